@@ -0,0 +1,29 @@
+//! Build script locating the front-end bundle compiled into the binary by the `embed-assets`
+//! feature.
+//!
+//! The bundle is built separately (by `rhubarb-frontend`) and isn't tracked in this repository;
+//! `assets/bundle.js` links to its build output. Set `RHUBARB_BUNDLE_JS` to embed a bundle from
+//! elsewhere.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RHUBARB_BUNDLE_JS");
+    if env::var_os("CARGO_FEATURE_EMBED_ASSETS").is_none() {
+        return;
+    }
+    let bundle = match env::var_os("RHUBARB_BUNDLE_JS") {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(&env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"))
+            .join("assets").join("bundle.js"),
+    };
+    println!("cargo:rerun-if-changed={}", bundle.display());
+    let out = Path::new(&env::var_os("OUT_DIR").expect("OUT_DIR not set")).join("bundle.js");
+    if let Err(e) = fs::copy(&bundle, &out) {
+        panic!("\n\nthe `embed-assets` feature requires the front-end bundle, which could not be \
+            read from '{}': {}\nbuild rhubarb-frontend (so that assets/bundle.js exists), or set \
+            RHUBARB_BUNDLE_JS to the path of a built bundle.js\n\n", bundle.display(), e);
+    }
+}
@@ -21,6 +21,9 @@ impl GraphElemField {
             attrs
         }
     }
+    pub fn name(&self) -> &syn::Ident {
+        &self.name
+    }
     pub fn extract_option_type<'a>(&'a self) -> Option<&'a syn::Type> {
         match self.ty {
             syn::Type::Path(syn::TypePath { ref path, .. }) => {
@@ -160,6 +163,16 @@ impl<'a> ToTokens for BuildExistingFieldAdder<'a> {
     }
 }
 
+impl GraphElemField {
+    /// Name used for this field in serialized output.
+    fn serialized_name(&self) -> String {
+        match self.attrs.serialize_rename {
+            Some(ref renamed) => renamed.clone(),
+            None => self.name.to_string()
+        }
+    }
+}
+
 pub struct BuildSerializer<'a>(&'a GraphElemField);
 impl<'a> From<&'a GraphElemField> for BuildSerializer<'a> {
     fn from(other: &'a GraphElemField) -> BuildSerializer<'a> {
@@ -169,11 +182,8 @@ impl<'a> From<&'a GraphElemField> for BuildSerializer<'a> {
 
 impl<'a> ToTokens for BuildSerializer<'a> {
     fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
-        let GraphElemField { ref name, ref attrs, .. } = self.0;
-        let name_str = match attrs.serialize_rename {
-            Some(ref renamed) => renamed.clone(),
-            None => name.to_string()
-        };
+        let GraphElemField { ref name, .. } = self.0;
+        let name_str = self.0.serialized_name();
         if self.0.extract_option_type().is_some() {
             quote!(
                 if let Some(ref value) = self.#name {
@@ -187,3 +197,21 @@ impl<'a> ToTokens for BuildSerializer<'a> {
         }
     }
 }
+
+pub struct BuildDeserializeField<'a>(&'a GraphElemField);
+impl<'a> From<&'a GraphElemField> for BuildDeserializeField<'a> {
+    fn from(other: &'a GraphElemField) -> BuildDeserializeField<'a> {
+        BuildDeserializeField(other)
+    }
+}
+
+impl<'a> ToTokens for BuildDeserializeField<'a> {
+    fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
+        let GraphElemField { ref name, ref ty, .. } = self.0;
+        let name_str = self.0.serialized_name();
+        quote!(
+            #[serde(rename = #name_str)]
+            #name: #ty,
+        ).to_tokens(tokens);
+    }
+}
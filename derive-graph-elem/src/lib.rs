@@ -1,4 +1,4 @@
-#![recursion_limit="128"]
+#![recursion_limit="256"]
 
 extern crate proc_macro;
 extern crate proc_macro2;
@@ -8,7 +8,8 @@ extern crate syn;
 use proc_macro::TokenStream;
 
 mod field;
-use field::{GraphElemField, BuildSetters, BuildExistingFieldAdder, BuildSerializer};
+use field::{GraphElemField, BuildSetters, BuildExistingFieldAdder, BuildSerializer,
+    BuildDeserializeField};
 
 #[proc_macro_derive(GraphElem, attributes(graphelem))]
 pub fn graph_elem_derive(input: TokenStream) -> TokenStream {
@@ -44,6 +45,11 @@ fn impl_graph_elem(ast: &syn::DeriveInput) -> TokenStream {
     // generate body of serialize implementation
     let field_serializers = graph_elem_fields.iter().map(BuildSerializer::from);
 
+    // generate fields of the intermediate struct used for deserialization
+    let deserialize_fields = graph_elem_fields.iter().map(BuildDeserializeField::from);
+    let field_names = graph_elem_fields.iter().map(|field| field.name());
+    let field_names_rhs = graph_elem_fields.iter().map(|field| field.name());
+
     // put everything together
     let tokens = quote!(
         impl #name { #(#field_setters)* }
@@ -62,6 +68,16 @@ fn impl_graph_elem(ast: &syn::DeriveInput) -> TokenStream {
                 state.end()
             }
         }
+        impl<'de> Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: Deserializer<'de>
+            {
+                #[derive(Deserialize)]
+                struct GraphElemFields { #(#deserialize_fields)* }
+                let fields = GraphElemFields::deserialize(deserializer)?;
+                Ok(#name { #(#field_names: fields.#field_names_rhs),* })
+            }
+        }
     );
     tokens.into()
 }
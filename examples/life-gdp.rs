@@ -2,6 +2,7 @@
 extern crate rhubarb;
 extern crate rhubarb_graph as rg;
 extern crate agnes;
+extern crate env_logger;
 
 use agnes::apply::{ApplyUnchecked, Select, Unique};
 use agnes::{DataView, MaybeNa, Filter};
@@ -13,6 +14,7 @@ use rhubarb::update::Update;
 use rhubarb::layout::{Layout, Component, ComponentIndex};
 use rhubarb::control::button::{ButtonControl, ButtonClick};
 use rhubarb::control::slider::{SliderControl, SliderChange};
+use rhubarb::control::timer::{TimerControl, Tick};
 use rhubarb::control::Control;
 
 use rg::scatter::Scatter;
@@ -134,13 +136,15 @@ fn generate_region_traces(dv: &DataView, year: &str) -> Result<Vec<Scatter>> {
 }
 
 fn generate_update(dv: &DataView, years: &Vec<&str>, slider_index: ComponentIndex,
-    layout: &Layout<UiState>, prev_state: Option<UiState>, state: UiState)
+    timer_index: ComponentIndex, layout: &Layout<UiState>, prev_state: Option<UiState>,
+    state: UiState)
     -> Result<Update<UiState>>
 {
-    let update_chart = if let Some(prev_state) = prev_state {
-        prev_state.year_idx != state.year_idx
+    // the timer starts out stopped, so sync it with the initial (possibly shared) state
+    let (update_chart, update_timer) = if let Some(prev_state) = prev_state {
+        (prev_state.year_idx != state.year_idx, prev_state.playing != state.playing)
     } else {
-        true
+        (true, true)
     };
     let chart = if update_chart {
         let scatter_traces = generate_region_traces(dv, years[state.year_idx])?;
@@ -172,15 +176,19 @@ fn generate_update(dv: &DataView, years: &Vec<&str>, slider_index: ComponentInde
     } else {
         None
     };
-    let mut new_slider = layout[slider_index].clone();
-    if let Component::Control(Control::Slider(ref mut slider)) = new_slider {
-        slider.curr_value = state.year_idx.clone();
-    } else {
-        panic!("unexpected component at slider index");
-    };
-
+    let (year_idx, playing) = (state.year_idx, state.playing);
     let mut update = Update::new(chart, state);
-    update.add_component(slider_index, new_slider)?;
+    update.edit_component(layout, slider_index, |component| {
+        if let Component::Control(Control::Slider(ref mut slider)) = *component {
+            slider.curr_value = year_idx;
+        } else {
+            panic!("unexpected component at slider index");
+        }
+        Ok(())
+    })?;
+    if update_timer {
+        update.set_timer_running(layout, timer_index, playing)?;
+    }
 
     Ok(update)
 }
@@ -240,14 +248,17 @@ fn generate_update(dv: &DataView, years: &Vec<&str>, slider_index: ComponentInde
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct UiState {
     year_idx: usize,
+    playing: bool,
 }
 impl Default for UiState {
     fn default() -> UiState {
-        UiState { year_idx: 0 }
+        UiState { year_idx: 0, playing: false }
     }
 }
 
 pub fn main() -> Result<()> {
+    env_logger::init();
+
     let years = vec!["1975", "1985", "1995", "2005", "2015"];
     let years_len = years.len();
     let dv = load_gdp_life_csv(&years).expect("error loading data sets");
@@ -273,14 +284,36 @@ pub fn main() -> Result<()> {
         ),
         main_panel
     )?;
+    // play through the years, one per second
+    layout.add_control_to_panel("play_button",
+        ButtonControl::new("Play / Pause").with_on_click(
+            |_: ButtonClick, mut state: UiState| -> Result<UiState> {
+                state.playing = !state.playing;
+                Ok(state)
+            }
+        ),
+        main_panel
+    )?;
+    let timer_index = layout.add_control_to_panel("play_timer",
+        TimerControl::new(1000).with_on_tick(
+            move |_: Tick, mut state: UiState| -> Result<UiState> {
+                // ignore any ticks sent before the client learned the timer was stopped
+                if state.playing {
+                    state.year_idx = (state.year_idx + 1) % years_len;
+                }
+                Ok(state)
+            }
+        ),
+        main_panel
+    )?;
 
     let app = RhubarbApp::new(
         layout,
-    );
+    ).title("Life Expectancy and GDP");
 
     app.start(move |layout: &Layout<UiState>, prev_state: Option<UiState>, state: UiState|
-        generate_update(&dv, &years, slider_index, layout, prev_state, state)
-    );
+        generate_update(&dv, &years, slider_index, timer_index, layout, prev_state, state)
+    )?;
     Ok(())
     // let app = RhubarbApp::new(
     //     chart,
@@ -1,4 +1,5 @@
 use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserialize, Deserializer};
 use palette::Srgb;
 use num_traits::float::Float;
 
@@ -24,6 +25,25 @@ impl Serialize for Color {
     }
 }
 
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Color, D::Error> where D: Deserializer<'de> {
+        let s = String::deserialize(deserializer)?;
+        let invalid = || {
+            <D::Error as de::Error>::invalid_value(de::Unexpected::Str(&s),
+                &"an 'rgb(r,g,b)' color")
+        };
+        let components = s.trim().trim_left_matches("rgb(").trim_right_matches(')')
+            .split(',')
+            .map(|c| c.trim().parse::<f32>().map(|c| c / 255.0))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        if !s.trim().starts_with("rgb(") || components.len() != 3 {
+            return Err(invalid());
+        }
+        Ok(Color::new(components[0], components[1], components[2]))
+    }
+}
+
 pub mod name {
     use super::Color;
 
@@ -1,4 +1,5 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
+use serde::de::{self, Deserialize, Deserializer};
 
 use CountExistFields;
 
@@ -15,6 +16,16 @@ impl Serialize for AxisKind {
         }
     }
 }
+impl<'de> Deserialize<'de> for AxisKind {
+    fn deserialize<D>(deserializer: D) -> Result<AxisKind, D::Error> where D: Deserializer<'de> {
+        let kind = String::deserialize(deserializer)?;
+        match kind.as_str() {
+            "linear" => Ok(AxisKind::Linear),
+            "log" => Ok(AxisKind::Log),
+            other => Err(de::Error::unknown_variant(other, &["linear", "log"])),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Default, GraphElem)]
 pub struct Axis {
@@ -1,9 +1,10 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
+use serde::de::{self, Deserialize, Deserializer};
 
 use CountExistFields;
 use color::Color;
 use SingleOrMore;
-use agnes::view::FieldView;
+use data::Series;
 
 #[derive(Debug, Clone, Default, GraphElem)]
 pub struct Line {
@@ -25,6 +26,14 @@ impl Mode {
             Mode::Text => "text",
         }.to_string()
     }
+    fn from_str(s: &str) -> Option<Mode> {
+        match s {
+            "lines" => Some(Mode::Lines),
+            "markers" => Some(Mode::Markers),
+            "text" => Some(Mode::Text),
+            _ => None
+        }
+    }
 }
 #[derive(Clone, Debug)]
 pub struct Modes(Vec<Mode>);
@@ -34,6 +43,18 @@ impl Modes {
     }
 }
 
+impl<'de> Deserialize<'de> for Modes {
+    fn deserialize<D>(deserializer: D) -> Result<Modes, D::Error> where D: Deserializer<'de> {
+        let modes = String::deserialize(deserializer)?;
+        modes.split('+')
+            .map(|mode| Mode::from_str(mode).ok_or_else(|| {
+                de::Error::unknown_variant(mode, &["lines", "markers", "text"])
+            }))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Modes)
+    }
+}
+
 impl From<Mode> for Modes {
     fn from(mode: Mode) -> Modes {
         Modes(vec![mode])
@@ -45,7 +66,7 @@ impl From<Vec<Mode>> for Modes {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Margin {
     pub l: usize,
     pub r: usize,
@@ -88,11 +109,27 @@ impl Serialize for Symbol {
         }
     }
 }
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D>(deserializer: D) -> Result<Symbol, D::Error> where D: Deserializer<'de> {
+        const SYMBOLS: &[&str] = &["circle", "circle-open", "square", "square-open", "x",
+            "x-open"];
+        let symbol = String::deserialize(deserializer)?;
+        match symbol.as_str() {
+            "circle" => Ok(Symbol::Circle),
+            "circle-open" => Ok(Symbol::CircleOpen),
+            "square" => Ok(Symbol::Square),
+            "square-open" => Ok(Symbol::SquareOpen),
+            "x" => Ok(Symbol::X),
+            "x-open" => Ok(Symbol::XOpen),
+            other => Err(de::Error::unknown_variant(other, SYMBOLS)),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Default, GraphElem)]
 pub struct Marker {
     symbol: Option<Symbol>,
     color: Option<Color>,
     line: Option<Line>,
-    size: Option<SingleOrMore<f64, FieldView>>
+    size: Option<SingleOrMore<f64, Series>>
 }
@@ -0,0 +1,93 @@
+use serde::ser::{Serialize, Serializer};
+use serde::de::{Deserialize, Deserializer};
+
+use agnes::DataView;
+use serde_json;
+
+use error::{self, GraphError};
+
+/// A single owned data value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Datum {
+    Number(f64),
+    Text(String),
+    Na,
+}
+impl From<f64> for Datum {
+    fn from(value: f64) -> Datum { Datum::Number(value) }
+}
+impl From<String> for Datum {
+    fn from(value: String) -> Datum { Datum::Text(value) }
+}
+impl<'a> From<&'a str> for Datum {
+    fn from(value: &'a str) -> Datum { Datum::Text(value.to_string()) }
+}
+impl<T: Into<Datum>> From<Option<T>> for Datum {
+    fn from(value: Option<T>) -> Datum {
+        value.map(|v| v.into()).unwrap_or(Datum::Na)
+    }
+}
+
+/// A column of data used by a graph element: either a view into a data frame, or owned values
+/// (such as those loaded from a serialized chart specification).
+#[derive(Debug, Clone)]
+pub enum Series {
+    View(DataView),
+    Values(Vec<Datum>),
+}
+impl Series {
+    /// Whether this series contains a field to serialize.
+    pub fn exists(&self) -> bool {
+        match *self {
+            Series::View(ref dv) => dv.as_fieldview().is_some(),
+            Series::Values(_) => true,
+        }
+    }
+    /// Copy the values of this series. A view which doesn't contain a field yields no values.
+    pub fn values(&self) -> error::Result<Vec<Datum>> {
+        match *self {
+            Series::View(ref dv) => match dv.as_fieldview() {
+                Some(ref field) => {
+                    let value = serde_json::to_value(field)?;
+                    serde_json::from_value(value).map_err(|e| GraphError::InvalidData(
+                        format!("unable to read series values: {}", e)))
+                },
+                None => Ok(vec![]),
+            },
+            Series::Values(ref values) => Ok(values.clone()),
+        }
+    }
+}
+impl Default for Series {
+    fn default() -> Series {
+        Series::View(DataView::default())
+    }
+}
+impl From<DataView> for Series {
+    fn from(dv: DataView) -> Series {
+        Series::View(dv)
+    }
+}
+impl<T: Into<Datum>> From<Vec<T>> for Series {
+    fn from(values: Vec<T>) -> Series {
+        Series::Values(values.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+impl Serialize for Series {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        match *self {
+            Series::View(ref dv) => match dv.as_fieldview() {
+                Some(ref field) => field.serialize(serializer),
+                None => serializer.serialize_none(),
+            },
+            Series::Values(ref values) => values.serialize(serializer),
+        }
+    }
+}
+impl<'de> Deserialize<'de> for Series {
+    fn deserialize<D>(deserializer: D) -> Result<Series, D::Error> where D: Deserializer<'de> {
+        Ok(Series::Values(Vec::<Datum>::deserialize(deserializer)?))
+    }
+}
@@ -0,0 +1,56 @@
+use std::error::Error;
+use std::fmt;
+
+use serde_json;
+
+/// Error enum for rendering or converting graphs.
+#[derive(Debug)]
+pub enum GraphError {
+    /// Failure serializing the graph specification
+    Serialize(serde_json::Error),
+    /// Data which could not be rendered
+    InvalidData(String),
+    /// Feature not supported by the target backend
+    Unsupported {
+        backend: String,
+        feature: String,
+    },
+}
+
+/// Wrapper for graph-based results.
+pub type Result<T> = ::std::result::Result<T, GraphError>;
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GraphError::Serialize(ref err) => write!(f, "Serialization error: {}", err),
+            GraphError::InvalidData(ref s) => write!(f, "Invalid data: {}", s),
+            GraphError::Unsupported { ref backend, ref feature } => write!(f,
+                "Feature '{}' not supported by {} backend", feature, backend),
+        }
+    }
+}
+
+impl Error for GraphError {
+    fn description(&self) -> &str {
+        match *self {
+            GraphError::Serialize(ref err) => err.description(),
+            GraphError::InvalidData(_) => "invalid data",
+            GraphError::Unsupported { .. } => "unsupported feature",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            GraphError::Serialize(ref err) => Some(err),
+            GraphError::InvalidData(_) => None,
+            GraphError::Unsupported { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for GraphError {
+    fn from(err: serde_json::Error) -> GraphError {
+        GraphError::Serialize(err)
+    }
+}
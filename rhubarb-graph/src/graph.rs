@@ -2,9 +2,10 @@ use serde::ser::{Serialize, Serializer, SerializeStruct};
 
 use Layout;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Graph<D> {
     data: Vec<D>,
+    #[serde(default)]
     layout: Layout
 }
 impl<D> Graph<D> {
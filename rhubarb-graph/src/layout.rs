@@ -1,4 +1,5 @@
 use serde::ser::{Serialize, Serializer, SerializeStruct};
+use serde::de::{Deserialize, Deserializer};
 
 use CountExistFields;
 use common::{Axis, Margin, Legend};
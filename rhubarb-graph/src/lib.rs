@@ -2,11 +2,15 @@
 #[macro_use] extern crate derive_graph_elem;
 extern crate agnes;
 extern crate serde;
+extern crate serde_json;
 extern crate palette;
 extern crate num_traits;
 
 pub mod scatter;
 
+mod data;
+pub use data::{Series, Datum};
+
 mod layout;
 pub use layout::{Layout};
 
@@ -18,3 +22,7 @@ pub use traits::{SingleOrMore, CountExistFields};
 
 pub mod color;
 pub mod common;
+
+pub mod error;
+pub mod svg;
+pub mod vega_lite;
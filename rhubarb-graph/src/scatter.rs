@@ -1,31 +1,33 @@
 use serde::ser::{Serialize, Serializer, SerializeMap};
-
-use agnes::DataView;
+use serde::de::{Deserialize, Deserializer};
 
 use common::{Modes, Marker};
+use data::Series;
 use CountExistFields;
 
 #[derive(Debug, Clone, Default)]
 pub struct Scatter {
-    x: DataView,
-    y: DataView,
+    x: Series,
+    y: Series,
     mode: Option<Modes>,
     marker: Option<Marker>,
-    text: Option<DataView>,
+    text: Option<Series>,
     opacity: Option<f64>,
-    name: Option<String>
+    name: Option<String>,
+    selected_points: Option<Vec<usize>>
 }
 
 impl Scatter {
-    pub fn new(x: DataView, y: DataView) -> Scatter {
+    pub fn new<X: Into<Series>, Y: Into<Series>>(x: X, y: Y) -> Scatter {
         Scatter {
-            x: x,
-            y: y,
+            x: x.into(),
+            y: y.into(),
             mode: None,
             marker: None,
             text: None,
             opacity: None,
             name: None,
+            selected_points: None,
         }
     }
     pub fn mode<T: Into<Modes>>(mut self, t: T) -> Scatter {
@@ -36,7 +38,7 @@ impl Scatter {
         self.marker = Some(t.into());
         self
     }
-    pub fn text<T: Into<DataView>>(mut self, t: T) -> Scatter {
+    pub fn text<T: Into<Series>>(mut self, t: T) -> Scatter {
         self.text = Some(t.into());
         self
     }
@@ -48,20 +50,27 @@ impl Scatter {
         self.name = Some(name.as_ref().to_string());
         self
     }
+    /// Indices of the points of this trace which are selected; the remaining points are drawn
+    /// de-emphasized.
+    pub fn selected_points(mut self, points: Vec<usize>) -> Scatter {
+        self.selected_points = Some(points);
+        self
+    }
 }
 
 impl CountExistFields for Scatter {
     fn count_existing_fields(&self) -> usize {
         let mut count = 0;
-        if self.x.as_fieldview().is_some() { count += 1; }
-        if self.y.as_fieldview().is_some() { count += 1; }
+        if self.x.exists() { count += 1; }
+        if self.y.exists() { count += 1; }
         if self.mode.is_some() { count += 1; }
         if self.marker.is_some() { count += 1; }
         if let Some(ref text) = self.text {
-            if text.as_fieldview().is_some() { count += 1; }
+            if text.exists() { count += 1; }
         }
         if self.name.is_some() { count += 1; }
         if self.opacity.is_some() { count += 1; }
+        if self.selected_points.is_some() { count += 1; }
         count
     }
 }
@@ -69,15 +78,46 @@ impl CountExistFields for Scatter {
 impl Serialize for Scatter {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         let mut map = serializer.serialize_map(Some(self.count_existing_fields()))?;
-        if let Some(ref x) = self.x.as_fieldview() { map.serialize_entry("x", x)?; }
-        if let Some(ref y) = self.y.as_fieldview() { map.serialize_entry("y", y)?; }
+        if self.x.exists() { map.serialize_entry("x", &self.x)?; }
+        if self.y.exists() { map.serialize_entry("y", &self.y)?; }
         if let Some(ref mode) = self.mode { map.serialize_entry("mode", &mode.as_string())? ; }
         if let Some(ref marker) = self.marker { map.serialize_entry("marker", &marker)?; }
         if let Some(ref text) = self.text {
-            if let Some(ref text) = text.as_fieldview() { map.serialize_entry("text", text)?; }
+            if text.exists() { map.serialize_entry("text", text)?; }
         }
         if let Some(opacity) = self.opacity { map.serialize_entry("opacity", &opacity)?; }
         if let Some(ref name) = self.name { map.serialize_entry("name", &name)?; }
+        if let Some(ref points) = self.selected_points {
+            map.serialize_entry("selectedpoints", points)?;
+        }
         map.end()
     }
 }
+
+impl<'de> Deserialize<'de> for Scatter {
+    fn deserialize<D>(deserializer: D) -> Result<Scatter, D::Error> where D: Deserializer<'de> {
+        #[derive(Deserialize)]
+        struct ScatterFields {
+            x: Option<Series>,
+            y: Option<Series>,
+            mode: Option<Modes>,
+            marker: Option<Marker>,
+            text: Option<Series>,
+            opacity: Option<f64>,
+            name: Option<String>,
+            #[serde(rename = "selectedpoints")]
+            selected_points: Option<Vec<usize>>,
+        }
+        let fields = ScatterFields::deserialize(deserializer)?;
+        Ok(Scatter {
+            x: fields.x.unwrap_or_default(),
+            y: fields.y.unwrap_or_default(),
+            mode: fields.mode,
+            marker: fields.marker,
+            text: fields.text,
+            opacity: fields.opacity,
+            name: fields.name,
+            selected_points: fields.selected_points,
+        })
+    }
+}
@@ -0,0 +1,525 @@
+//! Server-side SVG rendering of scatter graphs.
+//!
+//! Graphs are rendered from their serialized (Plotly-style) specification, so rendering honours
+//! exactly the properties which would be sent to the browser.
+
+use std::fmt::Write;
+
+use serde_json::{self, Value};
+
+use Graph;
+use scatter::Scatter;
+use error::{GraphError, Result};
+
+const BACKEND: &str = "SVG";
+// defaults match those used by Plotly
+const DEFAULT_WIDTH: f64 = 700.0;
+const DEFAULT_HEIGHT: f64 = 450.0;
+const DEFAULT_MARGIN: Margins = Margins { l: 80.0, r: 80.0, t: 100.0, b: 80.0 };
+const DEFAULT_MARKER_SIZE: f64 = 6.0;
+const DEFAULT_LINE_WIDTH: f64 = 2.0;
+const UNSELECTED_OPACITY: f64 = 0.2;
+const DEFAULT_COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd",
+    "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf",
+];
+const FONT: &str = "font-family=\"sans-serif\" fill=\"#444\"";
+const LEGEND_ROW_HEIGHT: f64 = 20.0;
+
+impl Graph<Scatter> {
+    /// Render this graph as an SVG document.
+    pub fn to_svg(&self) -> Result<String> {
+        render(&serde_json::to_value(self)?)
+    }
+}
+
+/// Render a serialized scatter graph specification (with `data` and `layout` fields) as an SVG
+/// document.
+pub fn render(spec: &Value) -> Result<String> {
+    let layout = &spec["layout"];
+    let traces = match spec["data"] {
+        Value::Array(ref traces) => traces.iter().enumerate()
+            .map(|(i, trace)| Trace::from_spec(i, trace))
+            .collect::<Result<Vec<_>>>()?,
+        Value::Null => vec![],
+        _ => return Err(GraphError::InvalidData("graph data must be an array".into())),
+    };
+
+    let width = layout["width"].as_f64().unwrap_or(DEFAULT_WIDTH);
+    let height = layout["height"].as_f64().unwrap_or(DEFAULT_HEIGHT);
+    let margin = Margins::from_spec(&layout["margin"]);
+    let plot = Rect {
+        x: margin.l,
+        y: margin.t,
+        w: (width - margin.l - margin.r).max(1.0),
+        h: (height - margin.t - margin.b).max(1.0),
+    };
+
+    let x_axis = AxisSpec::from_spec(&layout["xaxis"]);
+    let y_axis = AxisSpec::from_spec(&layout["yaxis"]);
+    let x_scale = Scale::new(&x_axis,
+        traces.iter().flat_map(|t| t.points.iter().map(|p| p.x)), plot.x, plot.x + plot.w);
+    // svg y coordinates increase downwards
+    let y_scale = Scale::new(&y_axis,
+        traces.iter().flat_map(|t| t.points.iter().map(|p| p.y)), plot.y + plot.h, plot.y);
+
+    let mut svg = String::new();
+    write!(svg, concat!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" ",
+        "height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n"), w = width, h = height).unwrap();
+    write!(svg, "<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", width, height).unwrap();
+    write!(svg, concat!("<defs><clipPath id=\"plot-area\"><rect x=\"{}\" y=\"{}\" ",
+        "width=\"{}\" height=\"{}\"/></clipPath></defs>\n"), plot.x, plot.y, plot.w, plot.h)
+        .unwrap();
+
+    if let Some(title) = layout["title"].as_str() {
+        write!(svg, concat!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"17\" ",
+            "{}>{}</text>\n"), width / 2.0, margin.t / 2.0, FONT, escape(title)).unwrap();
+    }
+
+    draw_x_axis(&mut svg, &x_axis, &x_scale, &plot);
+    draw_y_axis(&mut svg, &y_axis, &y_scale, &plot);
+
+    svg.push_str("<g clip-path=\"url(#plot-area)\">\n");
+    for trace in &traces {
+        trace.draw(&mut svg, &x_scale, &y_scale);
+    }
+    svg.push_str("</g>\n");
+
+    let show_legend = layout["showlegend"].as_bool().unwrap_or(traces.len() > 1);
+    if show_legend && !traces.is_empty() {
+        draw_legend(&mut svg, &layout["legend"], &traces, &plot);
+    }
+
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Margins { l: f64, r: f64, t: f64, b: f64 }
+impl Margins {
+    fn from_spec(spec: &Value) -> Margins {
+        Margins {
+            l: spec["l"].as_f64().unwrap_or(DEFAULT_MARGIN.l),
+            r: spec["r"].as_f64().unwrap_or(DEFAULT_MARGIN.r),
+            t: spec["t"].as_f64().unwrap_or(DEFAULT_MARGIN.t),
+            b: spec["b"].as_f64().unwrap_or(DEFAULT_MARGIN.b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Rect { x: f64, y: f64, w: f64, h: f64 }
+
+#[derive(Debug, Clone)]
+struct AxisSpec {
+    title: Option<String>,
+    log: bool,
+}
+impl AxisSpec {
+    fn from_spec(spec: &Value) -> AxisSpec {
+        AxisSpec {
+            title: spec["title"].as_str().map(|s| s.to_string()),
+            log: spec["type"].as_str() == Some("log"),
+        }
+    }
+}
+
+/// Mapping from data values to pixel coordinates. For log axes, `min` and `max` are in log10
+/// space.
+#[derive(Debug, Clone)]
+struct Scale {
+    min: f64,
+    max: f64,
+    log: bool,
+    start: f64,
+    end: f64,
+}
+impl Scale {
+    fn new<I: Iterator<Item=f64>>(axis: &AxisSpec, values: I, start: f64, end: f64) -> Scale {
+        let log = axis.log;
+        let (min, max) = values
+            .filter_map(|v| transform(v, log))
+            .fold((None, None), |(min, max): (Option<f64>, Option<f64>), v| {
+                (Some(min.map_or(v, |m| m.min(v))), Some(max.map_or(v, |m| m.max(v))))
+            });
+        let (lo, hi) = match (min, max) {
+            (Some(lo), Some(hi)) if hi > lo => (lo, hi),
+            (Some(v), Some(_)) => (v - 1.0, v + 1.0),
+            _ => (0.0, 1.0),
+        };
+        let pad = (hi - lo) * 0.05;
+        Scale { min: lo - pad, max: hi + pad, log, start, end }
+    }
+    /// Map a value (in transformed space) to pixels.
+    fn to_px(&self, v: f64) -> f64 {
+        self.start + (v - self.min) / (self.max - self.min) * (self.end - self.start)
+    }
+    /// Map a data value to pixels, if representable on this scale.
+    fn map(&self, v: f64) -> Option<f64> {
+        transform(v, self.log).map(|v| self.to_px(v))
+    }
+    /// Tick positions (in transformed space) along with their labels.
+    fn ticks(&self) -> Vec<(f64, String)> {
+        if self.log {
+            let first = self.min.ceil() as i32;
+            let last = self.max.floor() as i32;
+            if last > first {
+                return (first..last + 1)
+                    .map(|e| (e as f64, format_number(10f64.powi(e))))
+                    .collect();
+            }
+            linear_ticks(self.min, self.max).into_iter()
+                .map(|t| (t, format_number(10f64.powf(t))))
+                .collect()
+        } else {
+            linear_ticks(self.min, self.max).into_iter()
+                .map(|t| (t, format_number(t)))
+                .collect()
+        }
+    }
+}
+
+fn transform(v: f64, log: bool) -> Option<f64> {
+    if !v.is_finite() {
+        None
+    } else if log {
+        if v > 0.0 { Some(v.log10()) } else { None }
+    } else {
+        Some(v)
+    }
+}
+
+fn linear_ticks(min: f64, max: f64) -> Vec<f64> {
+    let raw_step = (max - min) / 5.0;
+    if !(raw_step > 0.0) {
+        return vec![min];
+    }
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let normalized = raw_step / magnitude;
+    let step = magnitude * if normalized < 1.5 {
+        1.0
+    } else if normalized < 3.0 {
+        2.0
+    } else if normalized < 7.0 {
+        5.0
+    } else {
+        10.0
+    };
+    let mut ticks = vec![];
+    let mut tick = (min / step).ceil() * step;
+    while tick <= max + step * 1e-9 {
+        // avoid '-0' labels and accumulated floating point noise
+        ticks.push(if tick.abs() < step * 1e-9 { 0.0 } else { tick });
+        tick += step;
+    }
+    ticks
+}
+
+fn format_number(v: f64) -> String {
+    let abs = v.abs();
+    if abs != 0.0 && (abs >= 1e6 || abs < 1e-3) {
+        format!("{:e}", v)
+    } else {
+        let s = format!("{:.6}", v);
+        let s = s.trim_right_matches('0').trim_right_matches('.');
+        if s == "-0" { "0".into() } else { s.into() }
+    }
+}
+
+fn draw_x_axis(svg: &mut String, axis: &AxisSpec, scale: &Scale, plot: &Rect) {
+    let y = plot.y + plot.h;
+    write!(svg, "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#444\"/>\n",
+        plot.x, y, plot.x + plot.w, y).unwrap();
+    for (tick, label) in scale.ticks() {
+        let x = scale.to_px(tick);
+        write!(svg, concat!("<line x1=\"{x}\" y1=\"{top}\" x2=\"{x}\" y2=\"{bottom}\" ",
+            "stroke=\"#eee\"/>\n"), x = x, top = plot.y, bottom = y).unwrap();
+        write!(svg, concat!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"12\" ",
+            "{}>{}</text>\n"), x, y + 16.0, FONT, escape(&label)).unwrap();
+    }
+    if let Some(ref title) = axis.title {
+        write!(svg, concat!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"14\" ",
+            "{}>{}</text>\n"), plot.x + plot.w / 2.0, y + 40.0, FONT, escape(title)).unwrap();
+    }
+}
+
+fn draw_y_axis(svg: &mut String, axis: &AxisSpec, scale: &Scale, plot: &Rect) {
+    write!(svg, "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"#444\"/>\n",
+        plot.y, plot.y + plot.h, x = plot.x).unwrap();
+    for (tick, label) in scale.ticks() {
+        let y = scale.to_px(tick);
+        write!(svg, concat!("<line x1=\"{left}\" y1=\"{y}\" x2=\"{right}\" y2=\"{y}\" ",
+            "stroke=\"#eee\"/>\n"), left = plot.x, right = plot.x + plot.w, y = y).unwrap();
+        write!(svg, concat!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\" font-size=\"12\" ",
+            "{}>{}</text>\n"), plot.x - 6.0, y + 4.0, FONT, escape(&label)).unwrap();
+    }
+    if let Some(ref title) = axis.title {
+        let x = (plot.x - 50.0).max(14.0);
+        let y = plot.y + plot.h / 2.0;
+        write!(svg, concat!("<text x=\"{x}\" y=\"{y}\" text-anchor=\"middle\" font-size=\"14\" ",
+            "transform=\"rotate(-90 {x} {y})\" {}>{}</text>\n"), FONT, escape(title), x = x, y = y)
+            .unwrap();
+    }
+}
+
+fn draw_legend(svg: &mut String, spec: &Value, traces: &[Trace], plot: &Rect) {
+    let x = plot.x + spec["x"].as_f64().unwrap_or(1.02) * plot.w;
+    let y = plot.y + (1.0 - spec["y"].as_f64().unwrap_or(1.0)) * plot.h;
+    let max_name_len = traces.iter().map(|t| t.name.chars().count()).max().unwrap_or(0);
+    let width = 40.0 + 7.0 * max_name_len as f64;
+    let height = LEGEND_ROW_HEIGHT * traces.len() as f64 + 8.0;
+
+    let fill = spec["bgcolor"].as_str().unwrap_or("none");
+    let border = spec["bordercolor"].as_str().unwrap_or("#444");
+    let border_width = spec["borderwidth"].as_f64().unwrap_or(0.0);
+    write!(svg, concat!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" ",
+        "stroke=\"{}\" stroke-width=\"{}\"/>\n"), x, y, width, height, escape(fill), escape(border),
+        border_width)
+        .unwrap();
+    for (i, trace) in traces.iter().enumerate() {
+        let row_y = y + 4.0 + LEGEND_ROW_HEIGHT * (i as f64 + 0.5);
+        write!(svg, "<g opacity=\"{}\">\n", trace.opacity).unwrap();
+        if trace.mode.lines {
+            write!(svg, concat!("<line x1=\"{}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"{}\" ",
+                "stroke-width=\"{}\"/>\n"), x + 5.0, x + 35.0, escape(&trace.color), trace.line_width,
+                y = row_y).unwrap();
+        }
+        if trace.mode.markers {
+            trace.marker.draw(svg, x + 20.0, row_y, DEFAULT_MARKER_SIZE.max(8.0));
+        }
+        svg.push_str("</g>\n");
+        write!(svg, "<text x=\"{}\" y=\"{}\" font-size=\"12\" {}>{}</text>\n",
+            x + 40.0, row_y + 4.0, FONT, escape(&trace.name)).unwrap();
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ModeFlags {
+    lines: bool,
+    markers: bool,
+    text: bool,
+}
+impl ModeFlags {
+    fn from_spec(spec: &Value, num_points: usize) -> Result<ModeFlags> {
+        let mode = match spec.as_str() {
+            Some(mode) => mode,
+            // Plotly defaults to 'lines+markers' for small traces, and 'lines' otherwise
+            None => if num_points < 20 { "lines+markers" } else { "lines" },
+        };
+        let mut flags = ModeFlags { lines: false, markers: false, text: false };
+        for part in mode.split('+') {
+            match part {
+                "lines" => flags.lines = true,
+                "markers" => flags.markers = true,
+                "text" => flags.text = true,
+                "none" => {},
+                other => return Err(GraphError::InvalidData(format!("unknown mode '{}'", other))),
+            }
+        }
+        Ok(flags)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Shape {
+    Circle,
+    Square,
+    X,
+}
+
+#[derive(Debug, Clone)]
+struct MarkerStyle {
+    shape: Shape,
+    open: bool,
+    color: String,
+    line_color: Option<String>,
+    line_width: f64,
+}
+impl MarkerStyle {
+    fn draw(&self, svg: &mut String, x: f64, y: f64, size: f64) {
+        let r = size / 2.0;
+        let (fill, stroke, stroke_width) = if self.open || self.shape == Shape::X {
+            ("none".to_string(), self.color.clone(), self.line_width.max(1.0))
+        } else {
+            (self.color.clone(), self.line_color.clone().unwrap_or_else(|| "none".into()),
+                self.line_width)
+        };
+        let style = format!("fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"",
+            escape(&fill), escape(&stroke), stroke_width);
+        let result = match self.shape {
+            Shape::Circle => {
+                write!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>\n", x, y, r, style)
+            },
+            Shape::Square => {
+                write!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" {}/>\n",
+                    x - r, y - r, style, s = size)
+            },
+            Shape::X => {
+                write!(svg, "<path d=\"M{} {}L{} {}M{} {}L{} {}\" {}/>\n",
+                    x - r, y - r, x + r, y + r, x - r, y + r, x + r, y - r, style)
+            },
+        };
+        result.unwrap();
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Point {
+    x: f64,
+    y: f64,
+    size: f64,
+    text: Option<String>,
+    selected: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Trace {
+    name: String,
+    mode: ModeFlags,
+    marker: MarkerStyle,
+    color: String,
+    line_width: f64,
+    opacity: f64,
+    points: Vec<Point>,
+}
+impl Trace {
+    fn from_spec(idx: usize, spec: &Value) -> Result<Trace> {
+        let xs = numbers(&spec["x"], "x")?;
+        let ys = numbers(&spec["y"], "y")?;
+        let texts = match spec["text"] {
+            Value::Array(ref values) => values.iter().map(|v| match *v {
+                Value::String(ref s) => Some(s.clone()),
+                Value::Null => None,
+                ref other => Some(other.to_string()),
+            }).collect(),
+            Value::String(ref s) => vec![Some(s.clone()); xs.len()],
+            _ => vec![],
+        };
+        let marker = &spec["marker"];
+        let sizes = match marker["size"] {
+            Value::Array(_) => numbers(&marker["size"], "marker.size")?,
+            ref size => vec![Some(size.as_f64().unwrap_or(DEFAULT_MARKER_SIZE)); xs.len()],
+        };
+
+        let selected = match spec["selectedpoints"] {
+            Value::Array(ref values) => Some(values.iter().filter_map(|v| v.as_u64())
+                .map(|i| i as usize).collect::<Vec<_>>()),
+            _ => None,
+        };
+
+        let points = xs.iter().zip(ys.iter()).enumerate()
+            .filter_map(|(i, (x, y))| match (*x, *y) {
+                (Some(x), Some(y)) => Some(Point {
+                    x,
+                    y,
+                    size: sizes.get(i).cloned().and_then(|s| s).unwrap_or(DEFAULT_MARKER_SIZE),
+                    text: texts.get(i).cloned().and_then(|t| t),
+                    selected: selected.as_ref().map_or(true, |sel| sel.contains(&i)),
+                }),
+                _ => None
+            })
+            .collect::<Vec<_>>();
+
+        let color = single_color(&marker["color"], "marker.color")?
+            .unwrap_or_else(|| DEFAULT_COLORS[idx % DEFAULT_COLORS.len()].to_string());
+        let symbol = marker["symbol"].as_str().unwrap_or("circle");
+        let shape = match symbol.trim_right_matches("-open") {
+            "circle" => Shape::Circle,
+            "square" => Shape::Square,
+            "x" => Shape::X,
+            other => return Err(GraphError::InvalidData(format!("unknown symbol '{}'", other))),
+        };
+        let marker_style = MarkerStyle {
+            shape,
+            open: symbol.ends_with("-open"),
+            color: color.clone(),
+            line_color: single_color(&marker["line"]["color"], "marker.line.color")?,
+            line_width: marker["line"]["width"].as_f64().unwrap_or(0.0),
+        };
+
+        Ok(Trace {
+            name: spec["name"].as_str().map(|s| s.to_string())
+                .unwrap_or_else(|| format!("trace {}", idx)),
+            mode: ModeFlags::from_spec(&spec["mode"], xs.len())?,
+            marker: marker_style,
+            color,
+            line_width: DEFAULT_LINE_WIDTH,
+            opacity: spec["opacity"].as_f64().unwrap_or(1.0),
+            points,
+        })
+    }
+
+    fn draw(&self, svg: &mut String, x_scale: &Scale, y_scale: &Scale) {
+        let mapped = self.points.iter()
+            .filter_map(|p| match (x_scale.map(p.x), y_scale.map(p.y)) {
+                (Some(x), Some(y)) => Some((x, y, p)),
+                _ => None
+            })
+            .collect::<Vec<_>>();
+        write!(svg, "<g opacity=\"{}\">\n", self.opacity).unwrap();
+        if self.mode.lines && mapped.len() > 1 {
+            let path = mapped.iter().enumerate()
+                .map(|(i, &(x, y, _))| format!("{}{} {}", if i == 0 { "M" } else { "L" }, x, y))
+                .collect::<Vec<_>>()
+                .join("");
+            write!(svg, "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                path, escape(&self.color), self.line_width).unwrap();
+        }
+        if self.mode.markers {
+            for &(x, y, point) in &mapped {
+                if point.selected {
+                    self.marker.draw(svg, x, y, point.size);
+                } else {
+                    write!(svg, "<g opacity=\"{}\">\n", UNSELECTED_OPACITY).unwrap();
+                    self.marker.draw(svg, x, y, point.size);
+                    svg.push_str("</g>\n");
+                }
+            }
+        }
+        if self.mode.text {
+            for &(x, y, point) in &mapped {
+                if let Some(ref text) = point.text {
+                    // place text above markers, as Plotly does
+                    let offset = if self.mode.markers { point.size / 2.0 + 4.0 } else { 0.0 };
+                    write!(svg, concat!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" ",
+                        "font-size=\"12\" {}>{}</text>\n"), x, y - offset, FONT, escape(text))
+                        .unwrap();
+                }
+            }
+        }
+        svg.push_str("</g>\n");
+    }
+}
+
+fn numbers(spec: &Value, field: &str) -> Result<Vec<Option<f64>>> {
+    match *spec {
+        Value::Array(ref values) => values.iter().map(|v| match *v {
+            Value::Number(ref n) => Ok(n.as_f64()),
+            Value::Null => Ok(None),
+            Value::String(ref s) => s.parse::<f64>().map(Some).map_err(|_| {
+                GraphError::InvalidData(format!("non-numeric value '{}' in {}", s, field))
+            }),
+            ref other => Err(GraphError::InvalidData(
+                format!("non-numeric value '{}' in {}", other, field))),
+        }).collect(),
+        Value::Null => Ok(vec![]),
+        _ => Err(GraphError::InvalidData(format!("{} must be an array", field))),
+    }
+}
+
+/// A single color, or `None` if unspecified. Per-point colors (arrays) aren't supported.
+fn single_color(spec: &Value, field: &str) -> Result<Option<String>> {
+    match *spec {
+        Value::String(ref color) => Ok(Some(color.clone())),
+        Value::Null => Ok(None),
+        Value::Array(_) => Err(GraphError::Unsupported {
+            backend: BACKEND.into(),
+            feature: format!("per-point {}", field),
+        }),
+        ref other => Err(GraphError::InvalidData(format!("invalid {} '{}'", field, other))),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
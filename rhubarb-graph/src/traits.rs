@@ -1,14 +1,13 @@
 use serde::ser::{Serialize, Serializer};
 
-use agnes::SerializeAsVec;
-
-#[derive(Debug, Clone)]
-pub enum SingleOrMore<T: Serialize, D: SerializeAsVec> {
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum SingleOrMore<T: Serialize, D: Serialize> {
     Single(T),
     More(D)
 }
 
-impl<T: Serialize, D: SerializeAsVec> Serialize for SingleOrMore<T, D> {
+impl<T: Serialize, D: Serialize> Serialize for SingleOrMore<T, D> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         match *self {
             SingleOrMore::Single(ref s) => {
@@ -21,7 +20,7 @@ impl<T: Serialize, D: SerializeAsVec> Serialize for SingleOrMore<T, D> {
     }
 }
 
-impl<T: Serialize, D: SerializeAsVec> From<T> for SingleOrMore<T, D> {
+impl<T: Serialize, D: Serialize> From<T> for SingleOrMore<T, D> {
     fn from(value: T) -> SingleOrMore<T, D> {
         SingleOrMore::Single(value)
     }
@@ -0,0 +1,349 @@
+//! Conversion of scatter graphs to Vega-Lite specifications.
+//!
+//! Like SVG rendering, conversion works from the serialized (Plotly-style) graph specification.
+//! Any property in that specification without a Vega-Lite equivalent results in a
+//! `GraphError::Unsupported` error, rather than being silently dropped.
+
+use serde_json::{self, Map, Value};
+
+use Graph;
+use scatter::Scatter;
+use error::{GraphError, Result};
+
+pub const SCHEMA: &str = "https://vega.github.io/schema/vega-lite/v3.json";
+
+const BACKEND: &str = "Vega-Lite";
+// Plotly defaults, used to convert overall figure sizes into Vega-Lite plot sizes
+const DEFAULT_MARGIN: [(&str, f64); 4] = [("l", 80.0), ("r", 80.0), ("t", 100.0), ("b", 80.0)];
+const DEFAULT_COLORS: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd",
+    "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf",
+];
+const X_SHAPE: &str = "M-1,-1L1,1M-1,1L1,-1";
+// opacity of unselected points (relative to the trace opacity), as in SVG rendering
+const UNSELECTED_OPACITY: f64 = 0.2;
+
+const LAYOUT_FIELDS: &[&str] = &["title", "width", "height", "xaxis", "yaxis", "margin",
+    "showlegend", "legend", "autosize"];
+const AXIS_FIELDS: &[&str] = &["title", "type"];
+const LEGEND_FIELDS: &[&str] = &["bgcolor", "bordercolor", "borderwidth", "x", "y"];
+const MARGIN_FIELDS: &[&str] = &["l", "r", "t", "b"];
+const TRACE_FIELDS: &[&str] = &["x", "y", "mode", "marker", "text", "opacity", "name",
+    "selectedpoints"];
+const MARKER_FIELDS: &[&str] = &["symbol", "color", "line", "size"];
+const LINE_FIELDS: &[&str] = &["width", "color"];
+
+impl Graph<Scatter> {
+    /// Convert this graph into an equivalent Vega-Lite specification.
+    pub fn to_vega_lite(&self) -> Result<Value> {
+        convert(&serde_json::to_value(self)?)
+    }
+}
+
+/// Convert a serialized scatter graph specification (with `data` and `layout` fields) into a
+/// Vega-Lite specification.
+pub fn convert(spec: &Value) -> Result<Value> {
+    let layout = &spec["layout"];
+    check_fields(layout, LAYOUT_FIELDS, "layout")?;
+    let traces = match spec["data"] {
+        Value::Array(ref traces) => traces.clone(),
+        Value::Null => vec![],
+        _ => return Err(GraphError::InvalidData("graph data must be an array".into())),
+    };
+
+    let mut vl = Map::new();
+    vl.insert("$schema".into(), SCHEMA.into());
+    if let Some(title) = layout["title"].as_str() {
+        vl.insert("title".into(), title.into());
+    }
+
+    // plotly margins become vega-lite padding, and plotly figure sizes include the margins
+    check_fields(&layout["margin"], MARGIN_FIELDS, "layout.margin")?;
+    let margin = |side: &str| {
+        layout["margin"][side].as_f64().unwrap_or_else(|| {
+            DEFAULT_MARGIN.iter().find(|&&(s, _)| s == side).map(|&(_, m)| m).unwrap_or(0.0)
+        })
+    };
+    if let Some(width) = layout["width"].as_f64() {
+        vl.insert("width".into(), (width - margin("l") - margin("r")).max(1.0).into());
+    }
+    if let Some(height) = layout["height"].as_f64() {
+        vl.insert("height".into(), (height - margin("t") - margin("b")).max(1.0).into());
+    }
+    vl.insert("padding".into(), json_object(vec![
+        ("left", margin("l").into()),
+        ("right", margin("r").into()),
+        ("top", margin("t").into()),
+        ("bottom", margin("b").into()),
+    ]));
+    if layout["autosize"].as_bool() == Some(true) {
+        vl.insert("autosize".into(), json_object(vec![("type", "fit".into())]));
+    }
+
+    let x_encoding = axis_encoding("x", &layout["xaxis"], "layout.xaxis", &traces)?;
+    let y_encoding = axis_encoding("y", &layout["yaxis"], "layout.yaxis", &traces)?;
+
+    // trace names and colors are shared through a single color encoding, which provides the
+    // legend
+    let names = traces.iter().enumerate()
+        .map(|(i, trace)| trace["name"].as_str().map(|s| s.to_string())
+            .unwrap_or_else(|| format!("trace {}", i)))
+        .collect::<Vec<_>>();
+    let colors = traces.iter().enumerate()
+        .map(|(i, trace)| trace["marker"]["color"].as_str().map(|s| s.to_string())
+            .unwrap_or_else(|| DEFAULT_COLORS[i % DEFAULT_COLORS.len()].to_string()))
+        .collect::<Vec<_>>();
+    let show_legend = layout["showlegend"].as_bool().unwrap_or(traces.len() > 1);
+    let legend = if show_legend {
+        legend(&layout["legend"], &vl)?
+    } else {
+        Value::Null
+    };
+    let color_encoding = json_object(vec![
+        ("field", "trace".into()),
+        ("type", "nominal".into()),
+        ("scale", json_object(vec![
+            ("domain", names.clone().into()),
+            ("range", colors.into()),
+        ])),
+        ("legend", legend),
+    ]);
+
+    let mut layers = vec![];
+    for (i, trace) in traces.iter().enumerate() {
+        layers.extend(trace_layers(trace, &names[i], &x_encoding, &y_encoding, &color_encoding,
+            &format!("data[{}]", i))?);
+    }
+    vl.insert("layer".into(), Value::Array(layers));
+    Ok(Value::Object(vl))
+}
+
+fn check_fields(spec: &Value, known: &[&str], path: &str) -> Result<()> {
+    match *spec {
+        Value::Object(ref map) => {
+            for key in map.keys() {
+                if !known.contains(&key.as_str()) {
+                    return Err(unsupported(format!("{}.{}", path, key)));
+                }
+            }
+            Ok(())
+        },
+        Value::Null => Ok(()),
+        _ => Err(GraphError::InvalidData(format!("{} must be an object", path))),
+    }
+}
+
+fn unsupported<S: Into<String>>(feature: S) -> GraphError {
+    GraphError::Unsupported { backend: BACKEND.into(), feature: feature.into() }
+}
+
+fn json_object(fields: Vec<(&str, Value)>) -> Value {
+    Value::Object(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+/// Encoding of the `field` (`x` or `y`) axis. Axes are quantitative, unless any trace has
+/// non-numeric values for `field`.
+fn axis_encoding(field: &str, spec: &Value, path: &str, traces: &[Value]) -> Result<Value> {
+    check_fields(spec, AXIS_FIELDS, path)?;
+    let nominal = traces.iter().any(|trace| match trace[field] {
+        Value::Array(ref values) => values.iter().any(|v| !v.is_number() && !v.is_null()),
+        _ => false,
+    });
+    let mut encoding = Map::new();
+    encoding.insert("field".into(), field.into());
+    encoding.insert("type".into(), if nominal { "nominal" } else { "quantitative" }.into());
+    if let Some(title) = spec["title"].as_str() {
+        encoding.insert("title".into(), title.into());
+    }
+    match spec["type"].as_str() {
+        Some("log") if nominal => {
+            return Err(unsupported(format!("{}.type = log with non-numeric values", path)));
+        },
+        Some("log") => {
+            encoding.insert("scale".into(), json_object(vec![("type", "log".into())]));
+        },
+        Some("linear") | None => {},
+        Some(other) => return Err(unsupported(format!("{}.type = {}", path, other))),
+    }
+    Ok(Value::Object(encoding))
+}
+
+fn legend(spec: &Value, vl: &Map<String, Value>) -> Result<Value> {
+    check_fields(spec, LEGEND_FIELDS, "layout.legend")?;
+    let mut legend = Map::new();
+    if let Some(color) = spec["bgcolor"].as_str() {
+        legend.insert("fillColor".into(), color.into());
+    }
+    if let Some(color) = spec["bordercolor"].as_str() {
+        legend.insert("strokeColor".into(), color.into());
+    }
+    if let Some(width) = spec["borderwidth"].as_f64() {
+        legend.insert("strokeWidth".into(), width.into());
+    }
+    // plotly legend positions are fractions of the plot area, measured from the bottom left
+    let (x, y) = (spec["x"].as_f64(), spec["y"].as_f64());
+    if x.is_some() || y.is_some() {
+        let width = vl.get("width").and_then(|w| w.as_f64());
+        let height = vl.get("height").and_then(|h| h.as_f64());
+        match (width, height) {
+            (Some(width), Some(height)) => {
+                legend.insert("orient".into(), "none".into());
+                legend.insert("legendX".into(), (x.unwrap_or(1.02) * width).into());
+                legend.insert("legendY".into(), ((1.0 - y.unwrap_or(1.0)) * height).into());
+            },
+            _ => return Err(unsupported("layout.legend position without fixed width and height")),
+        }
+    }
+    Ok(Value::Object(legend))
+}
+
+fn trace_layers(trace: &Value, name: &str, x_encoding: &Value, y_encoding: &Value,
+    color_encoding: &Value, path: &str) -> Result<Vec<Value>>
+{
+    check_fields(trace, TRACE_FIELDS, path)?;
+    let marker = &trace["marker"];
+    check_fields(marker, MARKER_FIELDS, &format!("{}.marker", path))?;
+    let marker_line = &marker["line"];
+    check_fields(marker_line, LINE_FIELDS, &format!("{}.marker.line", path))?;
+
+    let xs = array(&trace["x"], &format!("{}.x", path))?;
+    let ys = array(&trace["y"], &format!("{}.y", path))?;
+    let texts = match trace["text"] {
+        Value::Array(ref texts) => Some(texts.clone()),
+        Value::Null => None,
+        ref text => Some(vec![text.clone(); xs.len()]),
+    };
+    let sizes = match marker["size"] {
+        Value::Array(ref sizes) => Some(sizes.clone()),
+        _ => None,
+    };
+    let selected = match trace["selectedpoints"] {
+        Value::Array(ref points) => Some(points.iter().filter_map(|p| p.as_u64())
+            .map(|p| p as usize).collect::<Vec<_>>()),
+        Value::Null => None,
+        _ => return Err(GraphError::InvalidData(format!("{}.selectedpoints must be an array",
+            path))),
+    };
+
+    // data records for this trace
+    let values = xs.iter().zip(ys.iter()).enumerate().map(|(i, (x, y))| {
+        let mut record = vec![
+            ("trace", name.into()),
+            ("x", x.clone()),
+            ("y", y.clone()),
+        ];
+        if let Some(ref texts) = texts {
+            record.push(("text", texts.get(i).cloned().unwrap_or(Value::Null)));
+        }
+        if let Some(ref sizes) = sizes {
+            // plotly sizes are diameters, vega-lite sizes are areas
+            record.push(("size", sizes.get(i).and_then(|s| s.as_f64())
+                .map(|s| (s * s).into()).unwrap_or(Value::Null)));
+        }
+        if let Some(ref selected) = selected {
+            record.push(("selected", selected.contains(&i).into()));
+        }
+        json_object(record)
+    }).collect::<Vec<_>>();
+    let data = json_object(vec![("values", Value::Array(values))]);
+
+    let mut base_encoding = vec![
+        ("x", x_encoding.clone()),
+        ("y", y_encoding.clone()),
+        ("color", color_encoding.clone()),
+    ];
+    if trace["text"].is_array() {
+        base_encoding.push(("tooltip", json_object(vec![
+            ("field", "text".into()),
+            ("type", "nominal".into()),
+        ])));
+    }
+    let opacity = trace["opacity"].as_f64();
+
+    let mode = match trace["mode"].as_str() {
+        Some(mode) => mode.to_string(),
+        None => if xs.len() < 20 { "lines+markers".into() } else { "lines".into() },
+    };
+    let mut layers = vec![];
+    for part in mode.split('+') {
+        let mut mark = Map::new();
+        let mut encoding = base_encoding.clone();
+        match part {
+            "markers" => {
+                mark.insert("type".into(), "point".into());
+                let symbol = marker["symbol"].as_str().unwrap_or("circle");
+                let shape = match symbol.trim_right_matches("-open") {
+                    "circle" => "circle",
+                    "square" => "square",
+                    "x" => X_SHAPE,
+                    other => return Err(unsupported(format!("{}.marker.symbol = {}", path,
+                        other))),
+                };
+                mark.insert("shape".into(), shape.into());
+                mark.insert("filled".into(), (!symbol.ends_with("-open") && symbol != "x").into());
+                match marker["size"] {
+                    Value::Number(ref size) => {
+                        let size = size.as_f64().unwrap_or(0.0);
+                        mark.insert("size".into(), (size * size).into());
+                    },
+                    Value::Array(_) => {
+                        encoding.push(("size", json_object(vec![
+                            ("field", "size".into()),
+                            ("type", "quantitative".into()),
+                            ("scale", Value::Null),
+                            ("legend", Value::Null),
+                        ])));
+                    },
+                    _ => {},
+                }
+                if let Some(color) = marker_line["color"].as_str() {
+                    mark.insert("stroke".into(), color.into());
+                }
+                if let Some(width) = marker_line["width"].as_f64() {
+                    mark.insert("strokeWidth".into(), width.into());
+                }
+                // unselected points are de-emphasized
+                if selected.is_some() {
+                    let opacity = opacity.unwrap_or(1.0);
+                    encoding.push(("opacity", json_object(vec![
+                        ("condition", json_object(vec![
+                            ("test", "datum.selected".into()),
+                            ("value", opacity.into()),
+                        ])),
+                        ("value", (opacity * UNSELECTED_OPACITY).into()),
+                    ])));
+                }
+            },
+            "lines" => {
+                mark.insert("type".into(), "line".into());
+            },
+            "text" => {
+                mark.insert("type".into(), "text".into());
+                mark.insert("dy".into(), (-10).into());
+                encoding.push(("text", json_object(vec![
+                    ("field", "text".into()),
+                    ("type", "nominal".into()),
+                ])));
+            },
+            "none" => continue,
+            other => return Err(unsupported(format!("{}.mode = {}", path, other))),
+        }
+        if let Some(opacity) = opacity {
+            mark.insert("opacity".into(), opacity.into());
+        }
+        layers.push(json_object(vec![
+            ("data", data.clone()),
+            ("mark", Value::Object(mark)),
+            ("encoding", json_object(encoding)),
+        ]));
+    }
+    Ok(layers)
+}
+
+fn array(spec: &Value, path: &str) -> Result<Vec<Value>> {
+    match *spec {
+        Value::Array(ref values) => Ok(values.clone()),
+        Value::Null => Ok(vec![]),
+        _ => Err(GraphError::InvalidData(format!("{} must be an array", path))),
+    }
+}
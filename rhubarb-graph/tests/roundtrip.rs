@@ -0,0 +1,81 @@
+extern crate agnes;
+#[macro_use]
+extern crate serde_json;
+extern crate rhubarb_graph as rg;
+
+use rg::scatter::Scatter;
+use rg::common::{Line, Marker, Mode, Symbol};
+use std::path::Path;
+
+use agnes::source::{CsvReader, CsvSource};
+use agnes::view::DataView;
+
+#[test]
+fn graph_roundtrip() {
+    let data_filepath = Path::new("../").join(
+        Path::new(file!()).parent().unwrap().join("data/sample1.csv")
+    );
+    let source = CsvSource::new((&data_filepath).into()).unwrap();
+    let mut csv_rdr = CsvReader::new(&source).unwrap();
+    let dv: DataView = DataView::from(csv_rdr.read().unwrap()).v(["state", "val1", "val2"]);
+
+    let scatter = Scatter::new(
+            dv.v("val1"),
+            dv.v("val2")
+        )
+        .text(dv.v("state"))
+        .mode(vec![Mode::Markers, Mode::Text])
+        .marker(Marker::default()
+            .symbol(Symbol::SquareOpen)
+            .color(rg::color::name::red())
+            .size(12.0)
+            .line(Line::default().width(0.5).color(rg::color::name::white()))
+        )
+        .opacity(0.7)
+        .name("sample");
+    let graph: rg::Graph<Scatter> = rg::Graph::new(
+        vec![scatter],
+        rg::Layout::default()
+            .title("Sample Scatter Plot!")
+            .width(600usize)
+            .x_axis(rg::common::Axis::default()
+                .title("Value 1")
+                .kind(rg::common::AxisKind::Log)
+            )
+            .margin(rg::common::Margin::from_hv(10, 10))
+            .legend(rg::common::Legend::default().border_width(1).x(0.0))
+    );
+
+    let serialized = serde_json::to_string(&graph).unwrap();
+    println!("{}", serialized);
+    let loaded: rg::Graph<Scatter> = serde_json::from_str(&serialized).unwrap();
+    let reserialized = serde_json::to_value(&loaded).unwrap();
+
+    // data fields are loaded into owned values, so compare after one round trip
+    let reloaded: rg::Graph<Scatter> = serde_json::from_value(reserialized.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&reloaded).unwrap(), reserialized);
+
+    let original: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(reserialized["layout"], original["layout"]);
+    for field in &["mode", "marker", "opacity", "name", "text"] {
+        assert_eq!(reserialized["data"][0][field], original["data"][0][field]);
+    }
+    assert_eq!(reserialized["data"][0]["x"].as_array().unwrap().len(),
+        original["data"][0]["x"].as_array().unwrap().len());
+}
+
+#[test]
+fn owned_data() {
+    let graph: rg::Graph<Scatter> = serde_json::from_str(r#"{
+        "data": [{"x": [1, 2, 3], "y": [4.5, null, 6.5], "mode": "lines+markers"}],
+        "layout": {"title": "Owned", "xaxis": {"type": "linear"}}
+    }"#).unwrap();
+    let value = serde_json::to_value(&graph).unwrap();
+    assert_eq!(value["data"][0]["x"], json!([1.0, 2.0, 3.0]));
+    assert_eq!(value["data"][0]["y"], json!([4.5, null, 6.5]));
+    assert_eq!(value["data"][0]["mode"], "lines+markers");
+    assert_eq!(value["layout"]["title"], "Owned");
+
+    assert!(serde_json::from_str::<rg::Graph<Scatter>>(
+        r#"{"data": [{"x": [1], "y": [2], "mode": "bars"}]}"#).is_err());
+}
@@ -0,0 +1,194 @@
+extern crate agnes;
+extern crate rhubarb_graph as rg;
+#[macro_use]
+extern crate serde_json;
+
+use rg::scatter::Scatter;
+use rg::common::{Mode, Marker};
+use rg::error::GraphError;
+use std::path::Path;
+
+use agnes::source::{CsvReader, CsvSource};
+use agnes::view::DataView;
+
+#[test]
+fn svg_render() {
+    let data_filepath = Path::new("../").join(
+        Path::new(file!()).parent().unwrap().join("data/sample1.csv")
+    );
+    let source = CsvSource::new((&data_filepath).into()).unwrap();
+    let mut csv_rdr = CsvReader::new(&source).unwrap();
+    let dv: DataView = DataView::from(csv_rdr.read().unwrap()).v(["state", "val1", "val2"]);
+
+    let scatter = Scatter::new(
+            dv.v("val1"),
+            dv.v("val2")
+        )
+        .mode(Mode::Markers)
+        .marker(Marker::default().size(10.0).color(rg::color::name::red()))
+        .name("sample");
+
+    let graph: rg::Graph<Scatter> = rg::Graph::new(
+        vec![scatter],
+        rg::Layout::default()
+            .title("Sample Scatter Plot!")
+            .width(600usize)
+            .height(500usize)
+            .x_axis(rg::common::Axis::default()
+                .title("Value 1")
+                .kind(rg::common::AxisKind::Log)
+            )
+            .margin(rg::common::Margin::from_hv(10, 10))
+            .showlegend(true)
+    );
+    let svg = graph.to_svg().unwrap();
+    println!("{}", svg);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("width=\"600\" height=\"500\""));
+    assert!(svg.contains("Sample Scatter Plot!"));
+    assert!(svg.contains("Value 1"));
+    assert!(svg.contains("<circle"));
+    assert!(svg.contains(">sample</text>"));
+}
+
+#[test]
+fn svg_render_selected_points() {
+    let scatter = Scatter::new(vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0])
+        .mode(Mode::Markers)
+        .selected_points(vec![1]);
+    let serialized = serde_json::to_value(&scatter).unwrap();
+    assert_eq!(serialized["selectedpoints"], serde_json::Value::from(vec![1]));
+
+    let graph: rg::Graph<Scatter> = rg::Graph::new(vec![scatter], rg::Layout::default());
+    let svg = graph.to_svg().unwrap();
+    assert_eq!(svg.matches("<circle").count(), 3);
+    // only the two unselected points are dimmed
+    assert_eq!(svg.matches("<g opacity=\"0.2\">").count(), 2);
+}
+
+/// Numeric value of attribute `name` of an SVG element.
+fn attr(element: &str, name: &str) -> f64 {
+    let pattern = format!(" {}=\"", name);
+    let start = element.find(&pattern).unwrap() + pattern.len();
+    let end = start + element[start..].find('"').unwrap();
+    element[start..end].parse().unwrap()
+}
+
+/// Centres and radii of all circles in an SVG document.
+fn circles(svg: &str) -> Vec<(f64, f64, f64)> {
+    svg.lines()
+        .filter(|line| line.starts_with("<circle"))
+        .map(|line| (attr(line, "cx"), attr(line, "cy"), attr(line, "r")))
+        .collect()
+}
+
+fn assert_points(actual: &[(f64, f64, f64)], expected: &[(f64, f64, f64)]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a.0 - e.0).abs() < 1e-6 && (a.1 - e.1).abs() < 1e-6 && (a.2 - e.2).abs() < 1e-6,
+            "expected point {:?}, found {:?}", e, a);
+    }
+}
+
+// 240x240 graphs with 10px margins have a 220x220 plot area; with 5% padding either side, data
+// spanning 100 units maps 2px per unit
+fn square_layout(xaxis: serde_json::Value) -> serde_json::Value {
+    json!({
+        "width": 240,
+        "height": 240,
+        "margin": { "l": 10, "r": 10, "t": 10, "b": 10 },
+        "xaxis": xaxis,
+        "showlegend": false
+    })
+}
+
+#[test]
+fn svg_render_coordinates() {
+    let svg = rg::svg::render(&json!({
+        "data": [{
+            "x": [0, 50, 100],
+            "y": [0, 50, 100],
+            "mode": "markers",
+            "marker": { "size": 10, "color": "red" }
+        }],
+        "layout": square_layout(json!({}))
+    })).unwrap();
+    assert!(svg.contains("<rect x=\"10\" y=\"10\" width=\"220\" height=\"220\"/>"));
+    assert_points(&circles(&svg), &[(20.0, 220.0, 5.0), (120.0, 120.0, 5.0), (220.0, 20.0, 5.0)]);
+    assert_eq!(svg.matches("fill=\"red\"").count(), 3);
+    assert_eq!(svg.matches("<path").count(), 0);
+}
+
+#[test]
+fn svg_render_log_axis() {
+    let svg = rg::svg::render(&json!({
+        "data": [{
+            "x": [0, 1, 10, 100],
+            "y": [0, 0, 50, 100],
+            "mode": "markers"
+        }],
+        "layout": square_layout(json!({ "type": "log" }))
+    })).unwrap();
+    // the non-positive x value can't be shown on a log axis; log10(x) spans 0 to 2
+    assert_points(&circles(&svg), &[(20.0, 220.0, 3.0), (120.0, 120.0, 3.0), (220.0, 20.0, 3.0)]);
+}
+
+#[test]
+fn svg_render_lines_and_legend() {
+    let mut layout = square_layout(json!({}));
+    layout["showlegend"] = json!(true);
+    let svg = rg::svg::render(&json!({
+        "data": [
+            { "x": [0, 100], "y": [0, 100], "mode": "lines", "name": "up" },
+            { "x": [0, 100], "y": [100, 0], "mode": "lines", "name": "down" }
+        ],
+        "layout": layout
+    })).unwrap();
+    assert_eq!(svg.matches("<path").count(), 2);
+    assert_eq!(circles(&svg).len(), 0);
+    // traces use the default colours in order, in the plot and the legend
+    assert_eq!(svg.matches("stroke=\"#1f77b4\"").count(), 2);
+    assert_eq!(svg.matches("stroke=\"#ff7f0e\"").count(), 2);
+    assert!(svg.contains(">up</text>"));
+    assert!(svg.contains(">down</text>"));
+}
+
+#[test]
+fn svg_render_rejects_per_point_colors() {
+    let result = rg::svg::render(&json!({
+        "data": [{
+            "x": [1, 2],
+            "y": [1, 2],
+            "mode": "markers",
+            "marker": { "color": ["red", "blue"] }
+        }],
+        "layout": {}
+    }));
+    match result {
+        Err(GraphError::Unsupported { ref backend, ref feature }) => {
+            assert_eq!(backend, "SVG");
+            assert_eq!(feature, "per-point marker.color");
+        },
+        other => panic!("expected unsupported feature error, got {:?}", other),
+    }
+}
+
+#[test]
+fn svg_render_escapes_colors() {
+    let malicious = "red\" onload=\"alert(1)";
+    let mut layout = square_layout(json!({}));
+    layout["showlegend"] = json!(true);
+    layout["legend"] = json!({ "bgcolor": malicious, "bordercolor": malicious });
+    let svg = rg::svg::render(&json!({
+        "data": [{
+            "x": [0, 100],
+            "y": [0, 100],
+            "mode": "lines+markers",
+            "marker": { "color": malicious, "line": { "color": malicious, "width": 1 } }
+        }],
+        "layout": layout
+    })).unwrap();
+    assert!(!svg.contains("onload=\""), "{}", svg);
+    assert!(svg.contains("fill=\"red&quot; onload=&quot;alert(1)\""), "{}", svg);
+    assert!(svg.contains("stroke=\"red&quot; onload=&quot;alert(1)\""), "{}", svg);
+}
@@ -0,0 +1,117 @@
+extern crate agnes;
+extern crate serde_json;
+extern crate rhubarb_graph as rg;
+
+use rg::scatter::Scatter;
+use rg::common::{Mode, Marker};
+use rg::error::GraphError;
+use std::path::Path;
+
+use agnes::source::{CsvReader, CsvSource};
+use agnes::view::DataView;
+
+fn load_sample() -> DataView {
+    let data_filepath = Path::new("../").join(
+        Path::new(file!()).parent().unwrap().join("data/sample1.csv")
+    );
+    let source = CsvSource::new((&data_filepath).into()).unwrap();
+    let mut csv_rdr = CsvReader::new(&source).unwrap();
+    DataView::from(csv_rdr.read().unwrap()).v(["state", "val1", "val2"])
+}
+
+#[test]
+fn vega_lite_convert() {
+    let dv = load_sample();
+    let scatter = Scatter::new(
+            dv.v("val1"),
+            dv.v("val2")
+        )
+        .mode(vec![Mode::Markers, Mode::Text])
+        .marker(Marker::default().size(10.0))
+        .text(dv.v("state"))
+        .name("sample");
+
+    let graph: rg::Graph<Scatter> = rg::Graph::new(
+        vec![scatter],
+        rg::Layout::default()
+            .title("Sample Scatter Plot!")
+            .width(600usize)
+            .height(500usize)
+            .x_axis(rg::common::Axis::default()
+                .title("Value 1")
+                .kind(rg::common::AxisKind::Log)
+            )
+            .margin(rg::common::Margin::from_hv(10, 10))
+    );
+    let spec = graph.to_vega_lite().unwrap();
+    println!("{}", serde_json::to_string_pretty(&spec).unwrap());
+    assert_eq!(spec["title"], "Sample Scatter Plot!");
+    assert_eq!(spec["width"], 580.0);
+    assert_eq!(spec["height"], 480.0);
+    let layers = spec["layer"].as_array().unwrap();
+    // one layer each for markers and text
+    assert_eq!(layers.len(), 2);
+    assert_eq!(layers[0]["mark"]["type"], "point");
+    assert_eq!(layers[0]["mark"]["size"], 100.0);
+    assert_eq!(layers[0]["encoding"]["x"]["scale"]["type"], "log");
+    assert_eq!(layers[1]["mark"]["type"], "text");
+}
+
+#[test]
+fn vega_lite_unsupported() {
+    let spec = json_spec(r#"{"data": [], "layout": {"hovermode": "closest"}}"#);
+    match rg::vega_lite::convert(&spec) {
+        Err(GraphError::Unsupported { feature, .. }) => assert_eq!(feature, "layout.hovermode"),
+        other => panic!("expected unsupported feature error, got {:?}", other),
+    }
+}
+
+#[test]
+fn vega_lite_selected_points() {
+    let spec = json_spec(r#"{
+        "data": [{"x": [1, 2, 3], "y": [4, 5, 6], "mode": "markers+lines", "opacity": 0.5,
+            "selectedpoints": [0, 2]}],
+        "layout": {}
+    }"#);
+    let spec = rg::vega_lite::convert(&spec).unwrap();
+    let layers = spec["layer"].as_array().unwrap();
+    let values = layers[0]["data"]["values"].as_array().unwrap();
+    let selected = values.iter().map(|v| v["selected"].as_bool().unwrap()).collect::<Vec<_>>();
+    assert_eq!(selected, vec![true, false, true]);
+    let opacity = &layers[0]["encoding"]["opacity"];
+    assert_eq!(opacity["condition"]["test"], "datum.selected");
+    assert_eq!(opacity["condition"]["value"], 0.5);
+    assert_eq!(opacity["value"], 0.1);
+    // lines are drawn through all points
+    assert_eq!(layers[1]["mark"]["type"], "line");
+    assert!(layers[1]["encoding"]["opacity"].is_null());
+}
+
+#[test]
+fn vega_lite_nominal_axis() {
+    let spec = json_spec(r#"{
+        "data": [{"x": ["AL", "AK", "AZ"], "y": [4, 5, 6], "mode": "markers"}],
+        "layout": {}
+    }"#);
+    let spec = rg::vega_lite::convert(&spec).unwrap();
+    let layers = spec["layer"].as_array().unwrap();
+    assert_eq!(layers[0]["encoding"]["x"]["type"], "nominal");
+    assert_eq!(layers[0]["encoding"]["y"]["type"], "quantitative");
+    assert_eq!(layers[0]["data"]["values"][1]["x"], "AK");
+
+    // log scales need numeric values
+    let spec = json_spec(r#"{
+        "data": [{"x": ["AL", "AK", "AZ"], "y": [4, 5, 6], "mode": "markers"}],
+        "layout": {"xaxis": {"type": "log"}}
+    }"#);
+    match rg::vega_lite::convert(&spec) {
+        Err(GraphError::Unsupported { feature, .. }) => {
+            assert_eq!(feature, "layout.xaxis.type = log with non-numeric values");
+        },
+        other => panic!("expected unsupported feature error, got {:?}", other),
+    }
+}
+
+fn json_spec(s: &str) -> serde_json::Value {
+    serde_json::from_str(s).unwrap()
+}
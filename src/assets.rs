@@ -0,0 +1,123 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use mime::{self, Mime};
+
+/// Front-end assets required to serve a Rhubarb app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetKind {
+    BundleJs,
+    BundleCss,
+    BaseTemplate,
+}
+impl AssetKind {
+    /// Path of this asset, relative to the asset root directory.
+    pub fn path(&self) -> &'static str {
+        match *self {
+            AssetKind::BundleJs => "assets/bundle.js",
+            AssetKind::BundleCss => "assets/bundle.css",
+            AssetKind::BaseTemplate => "templates/base.hbs",
+        }
+    }
+    pub fn mime(&self) -> Mime {
+        match *self {
+            AssetKind::BundleJs => mime::TEXT_JAVASCRIPT,
+            AssetKind::BundleCss => mime::TEXT_CSS,
+            AssetKind::BaseTemplate => mime::TEXT_PLAIN,
+        }
+    }
+    #[cfg(feature = "embed-assets")]
+    fn embedded(&self) -> &'static [u8] {
+        match *self {
+            // copied into place by the build script, which fails if the bundle is missing
+            AssetKind::BundleJs => include_bytes!(concat!(env!("OUT_DIR"), "/bundle.js")),
+            AssetKind::BundleCss => include_bytes!("../assets/bundle.css"),
+            AssetKind::BaseTemplate => include_bytes!("../templates/base.hbs"),
+        }
+    }
+}
+
+/// Location front-end assets are loaded from.
+#[derive(Clone, Debug)]
+pub enum AssetSource {
+    /// Assets compiled into the binary. Only available with the `embed-assets` feature.
+    #[cfg(feature = "embed-assets")]
+    Embedded,
+    /// Assets read from disk (on every request) relative to the specified root directory. Useful
+    /// during front-end development.
+    Filesystem(PathBuf),
+}
+/// Environment variable setting the directory front-end assets are read from at runtime.
+pub const ASSETS_ENV_VAR: &str = "RHUBARB_ASSETS";
+
+impl Default for AssetSource {
+    /// Assets are read from the directory in the `RHUBARB_ASSETS` environment variable, if set.
+    /// Otherwise, they're embedded (with the `embed-assets` feature) or read from the working
+    /// directory.
+    fn default() -> AssetSource {
+        match env::var_os(ASSETS_ENV_VAR) {
+            Some(root) => AssetSource::Filesystem(root.into()),
+            None => AssetSource::fallback(),
+        }
+    }
+}
+impl AssetSource {
+    #[cfg(feature = "embed-assets")]
+    fn fallback() -> AssetSource { AssetSource::Embedded }
+    #[cfg(not(feature = "embed-assets"))]
+    fn fallback() -> AssetSource { AssetSource::Filesystem(PathBuf::from(".")) }
+    pub fn filesystem<P: AsRef<Path>>(root: P) -> AssetSource {
+        AssetSource::Filesystem(root.as_ref().into())
+    }
+    /// Whether assets from this source can change while the server is running.
+    pub fn is_static(&self) -> bool {
+        match *self {
+            #[cfg(feature = "embed-assets")]
+            AssetSource::Embedded => true,
+            AssetSource::Filesystem(_) => false,
+        }
+    }
+    pub fn load(&self, kind: AssetKind) -> io::Result<Asset> {
+        match *self {
+            #[cfg(feature = "embed-assets")]
+            AssetSource::Embedded => Ok(Asset::new(Cow::Borrowed(kind.embedded()), kind.mime())),
+            AssetSource::Filesystem(ref root) =>
+                Asset::from_file(root.join(kind.path()), kind.mime()),
+        }
+    }
+    pub fn load_string(&self, kind: AssetKind) -> io::Result<String> {
+        let asset = self.load(kind)?;
+        String::from_utf8(asset.contents.into_owned())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// A loaded asset, along with its entity tag.
+#[derive(Clone, Debug)]
+pub struct Asset {
+    pub contents: Cow<'static, [u8]>,
+    pub mime: Mime,
+    pub etag: String,
+}
+impl Asset {
+    /// Load an asset from the file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P, mime: Mime) -> io::Result<Asset> {
+        let mut buf = vec![];
+        File::open(path).and_then(|mut f| f.read_to_end(&mut buf))?;
+        Ok(Asset::new(Cow::Owned(buf), mime))
+    }
+    pub fn new(contents: Cow<'static, [u8]>, mime: Mime) -> Asset {
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Asset {
+            etag: format!("{:016x}", hasher.finish()),
+            contents,
+            mime,
+        }
+    }
+}
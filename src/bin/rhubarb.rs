@@ -0,0 +1,397 @@
+//! Serve a CSV file as an interactive explorer dashboard.
+//!
+//! Usage: `rhubarb <file.csv>`
+//!
+//! The dashboard plots one column against another, optionally coloring points by the values of a
+//! third column and sizing them by a fourth. Rows can be filtered by a column's value: numeric
+//! columns accept comparisons (`>10`, `<=2.5`, `=3`, `!=0`), and text columns match substrings.
+//! Views can be saved by name, and are stored next to the CSV file (in `<file>.views.json`).
+//! Changes can be undone and redone.
+//!
+//! Front-end assets are embedded with the `embed-assets` feature; otherwise, they're read from the
+//! working directory, or the directory in `RHUBARB_ASSETS` if set. The column and filter controls need a front-end
+//! bundle which renders `dropdown` and `text_input` controls (see FRONTEND.md).
+
+#[macro_use] extern crate serde_derive;
+extern crate rhubarb;
+extern crate rhubarb_graph as rg;
+extern crate agnes;
+
+use std::collections::HashMap;
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+
+use agnes::source::{CsvReader, CsvSource};
+use agnes::DataView;
+
+use rhubarb::{Chart, RhubarbApp};
+use rhubarb::error::*;
+use rhubarb::update::Update;
+use rhubarb::layout::{Layout, Panel, Component};
+use rhubarb::control::Control;
+use rhubarb::control::dropdown::{DropdownControl, DropdownChange};
+use rhubarb::control::text_input::{TextInputControl, TextChange};
+use rhubarb::control::view::{SaveViewControl, LoadViewControl};
+use rhubarb::control::history::HistoryControl;
+use rhubarb::store::{ViewStore, JsonFileStore};
+
+use rg::{Datum, Series, SingleOrMore};
+use rg::scatter::Scatter;
+use rg::common::{Mode, Marker};
+
+const NONE_OPTION: &str = "(none)";
+const CHART_TYPES: [&str; 3] = ["Scatter", "Line", "Line and markers"];
+const MIN_MARKER_SIZE: f64 = 6.0;
+const MAX_MARKER_SIZE: f64 = 30.0;
+const HISTORY_DEPTH: usize = 50;
+
+/// Columns of the loaded CSV file.
+#[derive(Debug)]
+struct Table {
+    names: Vec<String>,
+    columns: HashMap<String, Vec<Datum>>,
+    nrows: usize,
+}
+impl Table {
+    fn load(path: PathBuf) -> Result<Table> {
+        let mut csv_rdr = CsvReader::new(&CsvSource::new(path.into())?)?;
+        let dv: DataView = csv_rdr.read()?.into();
+        let names: Vec<String> = dv.fieldnames().iter().map(|name| name.to_string()).collect();
+        let mut columns = HashMap::new();
+        for name in &names {
+            columns.insert(name.clone(), Series::from(dv.v(name.as_str())).values()?);
+        }
+        Ok(Table { names, columns, nrows: dv.nrows() })
+    }
+    /// Column corresponding to the `idx`th option of a dropdown which has a leading 'none' option.
+    fn optional_column(&self, idx: usize) -> Option<&Vec<Datum>> {
+        if idx == 0 {
+            None
+        } else {
+            self.names.get(idx - 1).and_then(|name| self.columns.get(name))
+        }
+    }
+    fn column(&self, idx: usize) -> Option<&Vec<Datum>> {
+        self.names.get(idx).and_then(|name| self.columns.get(name))
+    }
+}
+
+/// Row filter entered by the user.
+#[derive(Debug, PartialEq)]
+enum RowFilter {
+    Compare(&'static str, f64),
+    Contains(String),
+}
+impl RowFilter {
+    fn parse(text: &str) -> Option<RowFilter> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        for op in &[">=", "<=", "!=", ">", "<", "="] {
+            if text.starts_with(op) {
+                if let Ok(value) = text[op.len()..].trim().parse() {
+                    return Some(RowFilter::Compare(*op, value));
+                }
+            }
+        }
+        Some(RowFilter::Contains(text.to_string()))
+    }
+    fn matches(&self, datum: &Datum) -> bool {
+        match (self, datum) {
+            (&RowFilter::Compare(op, value), &Datum::Number(x)) => match op {
+                ">=" => x >= value,
+                "<=" => x <= value,
+                "!=" => x != value,
+                ">" => x > value,
+                "<" => x < value,
+                _ => x == value,
+            },
+            (&RowFilter::Contains(ref text), &Datum::Text(ref s)) => s.contains(text.as_str()),
+            (&RowFilter::Contains(ref text), &Datum::Number(x)) => x.to_string() == *text,
+            _ => false,
+        }
+    }
+}
+
+fn label(datum: &Datum) -> String {
+    match *datum {
+        Datum::Number(x) => x.to_string(),
+        Datum::Text(ref s) => s.clone(),
+        Datum::Na => "NA".into(),
+    }
+}
+
+fn number(datum: &Datum) -> Option<f64> {
+    match *datum {
+        Datum::Number(x) => Some(x),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct ExplorerState {
+    x: usize,
+    y: usize,
+    color: usize,
+    size: usize,
+    chart_type: usize,
+    filter_column: usize,
+    filter: String,
+}
+impl Default for ExplorerState {
+    fn default() -> ExplorerState {
+        // plot the second column against the first
+        ExplorerState {
+            x: 0,
+            y: 1,
+            color: 0,
+            size: 0,
+            chart_type: 0,
+            filter_column: 0,
+            filter: String::new(),
+        }
+    }
+}
+
+fn generate_traces(table: &Table, state: &ExplorerState) -> Vec<Scatter> {
+    let (x, y) = match (table.column(state.x), table.column(state.y)) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return vec![],
+    };
+    let color = table.optional_column(state.color);
+    let size = table.optional_column(state.size);
+
+    // select the rows matching the filter
+    let mut rows: Vec<usize> = match (table.optional_column(state.filter_column),
+        RowFilter::parse(&state.filter))
+    {
+        (Some(column), Some(filter)) =>
+            (0..table.nrows).filter(|&row| filter.matches(&column[row])).collect(),
+        _ => (0..table.nrows).collect(),
+    };
+    let mode = match state.chart_type {
+        1 => vec![Mode::Lines],
+        2 => vec![Mode::Lines, Mode::Markers],
+        _ => vec![Mode::Markers],
+    };
+    if state.chart_type != 0 {
+        // lines should be drawn in order of the x-values
+        rows.sort_by(|&a, &b| match (number(&x[a]), number(&x[b])) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(::std::cmp::Ordering::Equal),
+            _ => label(&x[a]).cmp(&label(&x[b])),
+        });
+    }
+
+    // group rows into one trace per value of the color column
+    let mut groups: Vec<(String, Vec<usize>)> = vec![];
+    for row in rows {
+        let group = color.map(|column| label(&column[row])).unwrap_or_default();
+        match groups.iter().position(|&(ref name, _)| *name == group) {
+            Some(idx) => groups[idx].1.push(row),
+            None => groups.push((group, vec![row])),
+        }
+    }
+
+    // scale marker sizes over the entire (unfiltered) size column
+    let size_range = size.map(|column| {
+        column.iter().filter_map(number).fold((::std::f64::INFINITY, ::std::f64::NEG_INFINITY),
+            |(min, max), x| (min.min(x), max.max(x)))
+    });
+
+    groups.into_iter().map(|(name, rows)| {
+        let select = |column: &Vec<Datum>| -> Vec<Datum> {
+            rows.iter().map(|&row| column[row].clone()).collect()
+        };
+        let mut marker = Marker::default();
+        if let (Some(column), Some((min, max))) = (size, size_range) {
+            let sizes: Vec<Datum> = rows.iter().map(|&row| match number(&column[row]) {
+                Some(value) if max > min => Datum::Number(MIN_MARKER_SIZE
+                    + (value - min) / (max - min) * (MAX_MARKER_SIZE - MIN_MARKER_SIZE)),
+                _ => Datum::Number(MIN_MARKER_SIZE),
+            }).collect();
+            marker = marker.size(SingleOrMore::More(Series::from(sizes)));
+        }
+        let scatter = Scatter::new(select(x), select(y))
+            .mode(mode.clone())
+            .marker(marker);
+        if color.is_some() { scatter.name(name) } else { scatter }
+    }).collect()
+}
+
+fn generate_update(table: &Table, layout: &Layout<ExplorerState>, state: ExplorerState)
+    -> Result<Update<ExplorerState>>
+{
+    let traces = generate_traces(table, &state);
+    let x_title = table.names.get(state.x).cloned().unwrap_or_default();
+    let y_title = table.names.get(state.y).cloned().unwrap_or_default();
+    let chart = Chart::Scatter(rg::Graph::new(
+        traces,
+        rg::Layout::default()
+            .title(format!("{} vs. {}", y_title, x_title))
+            .width(800usize)
+            .height(500usize)
+            .autosize(true)
+            .x_axis(rg::common::Axis::default().title(x_title))
+            .y_axis(rg::common::Axis::default().title(y_title))
+            .margin(rg::common::Margin::from_hv(40, 40))
+            .showlegend(state.color != 0)
+    ));
+    let mut update = Update::new(chart, state);
+    // refresh the list of saved views, in case a view was just saved
+    if let Some(idx) = layout.get_component_index("load_view") {
+        update.edit_component(layout, idx, |component| match *component {
+            Component::Control(Control::LoadView(ref mut load_view)) => load_view.refresh(),
+            _ => Ok(()),
+        })?;
+    }
+    Ok(update)
+}
+
+fn build_layout(table: &Table, store: Arc<ViewStore>) -> Result<Layout<ExplorerState>> {
+    let columns = table.names.clone();
+    let mut optional_columns = vec![NONE_OPTION.to_string()];
+    optional_columns.extend(columns.iter().cloned());
+
+    let mut layout: Layout<ExplorerState> = Layout::default();
+    let columns_panel = layout.add_panel_with("columns_panel",
+        Panel::row().with_gap(8).with_wrap(true), None)?;
+    layout.add_control_to_panel("x_column",
+        DropdownControl::new(columns.clone(), 0).with_label("X").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { x: change.idx, ..state })
+            }
+        ),
+        columns_panel
+    )?;
+    layout.add_control_to_panel("y_column",
+        DropdownControl::new(columns.clone(), 1).with_label("Y").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { y: change.idx, ..state })
+            }
+        ),
+        columns_panel
+    )?;
+    layout.add_control_to_panel("color_column",
+        DropdownControl::new(optional_columns.clone(), 0).with_label("Colour").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { color: change.idx, ..state })
+            }
+        ),
+        columns_panel
+    )?;
+    layout.add_control_to_panel("size_column",
+        DropdownControl::new(optional_columns.clone(), 0).with_label("Size").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { size: change.idx, ..state })
+            }
+        ),
+        columns_panel
+    )?;
+    layout.add_control_to_panel("chart_type",
+        DropdownControl::new(CHART_TYPES.to_vec(), 0).with_label("Chart type").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { chart_type: change.idx, ..state })
+            }
+        ),
+        columns_panel
+    )?;
+
+    let filter_panel = layout.add_panel_with("filter_panel", Panel::row().with_gap(8), None)?;
+    layout.add_control_to_panel("filter_column",
+        DropdownControl::new(optional_columns, 0).with_label("Filter by").with_on_change(
+            |change: DropdownChange, state: ExplorerState| -> Result<ExplorerState> {
+                Ok(ExplorerState { filter_column: change.idx, ..state })
+            }
+        ),
+        filter_panel
+    )?;
+    layout.add_control_to_panel("filter",
+        TextInputControl::new("").with_label("Filter").with_placeholder("e.g. >10 or text")
+            .with_on_change(
+                |change: TextChange, state: ExplorerState| -> Result<ExplorerState> {
+                    Ok(ExplorerState { filter: change.value, ..state })
+                }
+            ),
+        filter_panel
+    )?;
+
+    let views_panel = layout.add_panel_with("views_panel", Panel::row().with_gap(8), None)?;
+    layout.add_control_to_panel("save_view",
+        SaveViewControl::new(store.clone()).with_label("Save view").with_placeholder("View name"),
+        views_panel
+    )?;
+    layout.add_control_to_panel("load_view",
+        LoadViewControl::new(store).with_label("Load view"),
+        views_panel
+    )?;
+    layout.add_control_to_panel("undo", HistoryControl::undo(), views_panel)?;
+    layout.add_control_to_panel("redo", HistoryControl::redo(), views_panel)?;
+    Ok(layout)
+}
+
+pub fn main() -> Result<()> {
+    let path = match env::args().nth(1) {
+        Some(path) => PathBuf::from(path),
+        None => {
+            eprintln!("usage: rhubarb <file.csv>");
+            process::exit(1);
+        }
+    };
+    let title = path.file_name().map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let store: Arc<ViewStore> = Arc::new(JsonFileStore::new(
+        path.with_extension("views.json")));
+    let table = Arc::new(Table::load(path)?);
+    if table.names.len() < 2 {
+        eprintln!("at least two columns are required to explore {}", title);
+        process::exit(1);
+    }
+
+    let app = RhubarbApp::new(build_layout(&table, store)?).title(title)
+        .history(HISTORY_DEPTH);
+    app.start(move |layout: &Layout<ExplorerState>, _: Option<ExplorerState>,
+        state: ExplorerState| generate_update(&table, layout, state)
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RowFilter;
+    use rg::Datum;
+
+    #[test]
+    fn parse_comparisons() {
+        assert_eq!(RowFilter::parse(">10"), Some(RowFilter::Compare(">", 10.0)));
+        assert_eq!(RowFilter::parse(" >= 2.5 "), Some(RowFilter::Compare(">=", 2.5)));
+        assert_eq!(RowFilter::parse("<= -1"), Some(RowFilter::Compare("<=", -1.0)));
+        assert_eq!(RowFilter::parse("!=0"), Some(RowFilter::Compare("!=", 0.0)));
+        assert_eq!(RowFilter::parse("=3"), Some(RowFilter::Compare("=", 3.0)));
+    }
+
+    #[test]
+    fn parse_text_and_empty() {
+        assert_eq!(RowFilter::parse(""), None);
+        assert_eq!(RowFilter::parse("   "), None);
+        assert_eq!(RowFilter::parse("asia"), Some(RowFilter::Contains("asia".into())));
+        // operators without a numeric operand match as text
+        assert_eq!(RowFilter::parse(">abc"), Some(RowFilter::Contains(">abc".into())));
+    }
+
+    #[test]
+    fn matches_by_type() {
+        let filter = RowFilter::parse(">10").unwrap();
+        assert!(filter.matches(&Datum::Number(11.0)));
+        assert!(!filter.matches(&Datum::Number(10.0)));
+        assert!(!filter.matches(&Datum::Text("20".into())));
+        assert!(!filter.matches(&Datum::Na));
+
+        let filter = RowFilter::parse("si").unwrap();
+        assert!(filter.matches(&Datum::Text("Asia".into())));
+        assert!(!filter.matches(&Datum::Text("Europe".into())));
+        assert!(RowFilter::parse("3").unwrap().matches(&Datum::Number(3.0)));
+    }
+}
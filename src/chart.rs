@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::fmt;
+
+use Chart;
+use event::{Event, EventHandler, HandleEvent};
+use error;
+use layout::{Component, ComponentIndex};
+use props::{CommonProps, CommonProperties};
+
+/// Location of a chart figure within a layout. The figure itself is provided by updates (see
+/// `Update::set_chart_at`), and interaction events (clicks and selections) are addressed to this
+/// component.
+#[derive(Clone, Serialize)]
+pub struct ChartComponent<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    #[serde(skip)]
+    on_click: Option<Arc<EventHandler<ChartClick, St>>>,
+    #[serde(skip)]
+    on_select: Option<Arc<EventHandler<ChartSelect, St>>>
+}
+impl<St> fmt::Debug for ChartComponent<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ChartComponent")
+            .field("common", &self.common)
+            .field("on_click", &match self.on_click {
+                Some(_) => "<<on_click handler>>",
+                None => "none"
+            }.to_string())
+            .field("on_select", &match self.on_select {
+                Some(_) => "<<on_select handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> ChartComponent<St> {
+    pub fn new() -> ChartComponent<St> {
+        ChartComponent {
+            common: CommonProps::default(),
+            on_click: None,
+            on_select: None
+        }
+    }
+    pub fn with_on_click<E: 'static + EventHandler<ChartClick, St>>(self, f: E)
+        -> ChartComponent<St>
+    {
+        ChartComponent {
+            on_click: Some(Arc::new(f)),
+            ..self
+        }
+    }
+    pub fn with_on_select<E: 'static + EventHandler<ChartSelect, St>>(self, f: E)
+        -> ChartComponent<St>
+    {
+        ChartComponent {
+            on_select: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for ChartComponent<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for ChartComponent<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::ChartClick(click_details) => {
+                match self.on_click {
+                    Some(ref handler) => handler.handle(click_details, state),
+                    None => Ok(state)
+                }
+            },
+            Event::ChartSelect(select_details) => {
+                match self.on_select {
+                    Some(ref handler) => handler.handle(select_details, state),
+                    None => Ok(state)
+                }
+            },
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "ChartComponent".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<ChartComponent<St>> for Component<St> {
+    fn from(chart: ChartComponent<St>) -> Component<St> { Component::Chart(chart) }
+}
+
+/// Reference to a single data point of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointRef {
+    /// Index of the trace containing the point.
+    pub trace: usize,
+    /// Index of the point within its trace.
+    pub point: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChartClick {
+    pub points: Vec<PointRef>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChartSelect {
+    pub points: Vec<PointRef>
+}
+
+/// A chart figure addressed to a chart component in the layout.
+#[derive(Debug, Serialize)]
+pub struct IndexedChart {
+    pub idx: ComponentIndex,
+    pub chart: Chart,
+}
@@ -0,0 +1,269 @@
+use std::sync::Arc;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+
+use event::{Event, EventHandler, HandleEvent};
+use error;
+use layout::{Component, ComponentIndex};
+use props::{CommonProps, CommonProperties};
+
+/// Components which group their children in ways other than a plain `Panel`. Children are added
+/// to containers the same way they are added to panels (e.g. with `Layout::add_panel`).
+#[derive(Debug, Clone)]
+pub enum Container<St> {
+    Tabs(Tabs<St>),
+    Accordion(Accordion),
+    Modal(Modal<St>),
+}
+impl<St> Container<St> {
+    pub fn children(&self) -> &[ComponentIndex] {
+        match *self {
+            Container::Tabs(ref tabs) => &tabs.children,
+            Container::Accordion(ref accordion) => &accordion.children,
+            Container::Modal(ref modal) => &modal.children,
+        }
+    }
+    pub(crate) fn children_mut(&mut self) -> &mut Vec<ComponentIndex> {
+        match *self {
+            Container::Tabs(ref mut tabs) => &mut tabs.children,
+            Container::Accordion(ref mut accordion) => &mut accordion.children,
+            Container::Modal(ref mut modal) => &mut modal.children,
+        }
+    }
+}
+impl<St> Serialize for Container<St> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut state = serializer.serialize_struct("Container", 2)?;
+        match *self {
+            Container::Tabs(ref tabs) => {
+                state.serialize_field("type", "tabs")?;
+                state.serialize_field("properties", tabs)?;
+            }
+            Container::Accordion(ref accordion) => {
+                state.serialize_field("type", "accordion")?;
+                state.serialize_field("properties", accordion)?;
+            }
+            Container::Modal(ref modal) => {
+                state.serialize_field("type", "modal")?;
+                state.serialize_field("properties", modal)?;
+            }
+        }
+        state.end()
+    }
+}
+impl<St> CommonProperties for Container<St> {
+    fn common(&self) -> &CommonProps {
+        match *self {
+            Container::Tabs(ref tabs) => &tabs.common,
+            Container::Accordion(ref accordion) => &accordion.common,
+            Container::Modal(ref modal) => &modal.common,
+        }
+    }
+    fn common_mut(&mut self) -> &mut CommonProps {
+        match *self {
+            Container::Tabs(ref mut tabs) => &mut tabs.common,
+            Container::Accordion(ref mut accordion) => &mut accordion.common,
+            Container::Modal(ref mut modal) => &mut modal.common,
+        }
+    }
+}
+impl<St> HandleEvent<St> for Container<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match *self {
+            Container::Tabs(ref tabs) => tabs.handle_event(event, state),
+            Container::Accordion(_) => Err(error::RhubarbError::InvalidEvent {
+                event,
+                component_type: "Accordion".into()
+            }),
+            Container::Modal(ref modal) => modal.handle_event(event, state),
+        }
+    }
+}
+impl<St> From<Container<St>> for Component<St> {
+    fn from(container: Container<St>) -> Component<St> { Component::Container(container) }
+}
+
+/// Container showing one child at a time, selected by a row of tabs. Each tab is labeled with
+/// its child's title. Tabs switched by the user are recorded in the client's session (see
+/// `Layout::sync_event`).
+#[derive(Clone, Serialize)]
+pub struct Tabs<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    pub active: usize,
+    children: Vec<ComponentIndex>,
+    #[serde(skip)]
+    on_change: Option<Arc<EventHandler<TabChange, St>>>
+}
+impl<St> fmt::Debug for Tabs<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Tabs")
+            .field("common", &self.common)
+            .field("active", &self.active)
+            .field("children", &self.children)
+            .field("on_change", &match self.on_change {
+                Some(_) => "<<on_change handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> Tabs<St> {
+    pub fn new(active: usize) -> Tabs<St> {
+        Tabs {
+            common: CommonProps::default(),
+            active,
+            children: vec![],
+            on_change: None
+        }
+    }
+    pub fn with_on_change<E: 'static + EventHandler<TabChange, St>>(self, f: E) -> Tabs<St> {
+        Tabs {
+            on_change: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for Tabs<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for Tabs<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::TabChange(change_details) => {
+                match self.on_change {
+                    Some(ref handler) => handler.handle(change_details, state),
+                    None => Ok(state)
+                }
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "Tabs".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<Tabs<St>> for Container<St> {
+    fn from(tabs: Tabs<St>) -> Container<St> { Container::Tabs(tabs) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TabChange {
+    pub idx: usize
+}
+
+/// Container of collapsible sections, one per child. Each section is headed by its child's title.
+#[derive(Debug, Clone, Serialize)]
+pub struct Accordion {
+    #[serde(flatten)]
+    common: CommonProps,
+    /// Children whose sections are initially expanded.
+    pub expanded: Vec<ComponentIndex>,
+    /// Whether more than one section can be expanded at a time.
+    multiple: bool,
+    children: Vec<ComponentIndex>,
+}
+impl Accordion {
+    pub fn new() -> Accordion {
+        Accordion {
+            common: CommonProps::default(),
+            expanded: vec![],
+            multiple: false,
+            children: vec![],
+        }
+    }
+    pub fn with_multiple(self, multiple: bool) -> Accordion {
+        Accordion { multiple, ..self }
+    }
+    pub fn with_expanded(self, expanded: Vec<ComponentIndex>) -> Accordion {
+        Accordion { expanded, ..self }
+    }
+}
+impl CommonProperties for Accordion {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> From<Accordion> for Container<St> {
+    fn from(accordion: Accordion) -> Container<St> { Container::Accordion(accordion) }
+}
+
+/// Dialog shown over the dashboard. Modals are opened and closed by updates (see
+/// `Update::set_modal_open`), and can optionally be closed by the user, which is recorded in the
+/// client's session (see `Layout::sync_event`).
+#[derive(Clone, Serialize)]
+pub struct Modal<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    pub open: bool,
+    closable: bool,
+    children: Vec<ComponentIndex>,
+    #[serde(skip)]
+    on_close: Option<Arc<EventHandler<ModalClose, St>>>
+}
+impl<St> fmt::Debug for Modal<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Modal")
+            .field("common", &self.common)
+            .field("open", &self.open)
+            .field("closable", &self.closable)
+            .field("children", &self.children)
+            .field("on_close", &match self.on_close {
+                Some(_) => "<<on_close handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> Modal<St> {
+    pub fn new() -> Modal<St> {
+        Modal {
+            common: CommonProps::default(),
+            open: false,
+            closable: true,
+            children: vec![],
+            on_close: None
+        }
+    }
+    pub fn with_closable(self, closable: bool) -> Modal<St> {
+        Modal { closable, ..self }
+    }
+    pub fn with_on_close<E: 'static + EventHandler<ModalClose, St>>(self, f: E) -> Modal<St> {
+        Modal {
+            on_close: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for Modal<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for Modal<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::ModalClose(close_details) => {
+                match self.on_close {
+                    Some(ref handler) => handler.handle(close_details, state),
+                    None => Ok(state)
+                }
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "Modal".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<Modal<St>> for Container<St> {
+    fn from(modal: Modal<St>) -> Container<St> { Container::Modal(modal) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModalClose {}
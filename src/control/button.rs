@@ -2,11 +2,14 @@ use std::sync::Arc;
 use std::fmt;
 
 use control::Control;
+use props::{CommonProps, CommonProperties};
 use event::{HandleEvent, Event, EventHandler};
 use error;
 
 #[derive(Clone, Serialize)]
 pub struct ButtonControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
     text: String,
     #[serde(skip)]
     on_click: Option<Arc<EventHandler<ButtonClick, St>>>
@@ -14,6 +17,7 @@ pub struct ButtonControl<St> {
 impl<St> fmt::Debug for ButtonControl<St> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("ButtonControl")
+            .field("common", &self.common)
             .field("text", &self.text)
             .field("on_click", &match self.on_click {
                 Some(_) => "<<on_click handler>>",
@@ -25,6 +29,7 @@ impl<St> fmt::Debug for ButtonControl<St> {
 impl<St> ButtonControl<St> {
     pub fn new<S: AsRef<str>>(text: S) -> ButtonControl<St> {
         ButtonControl {
+            common: CommonProps::default(),
             text: text.as_ref().into(),
             on_click: None
         }
@@ -33,11 +38,16 @@ impl<St> ButtonControl<St> {
         -> ButtonControl<St>
     {
         ButtonControl {
+            common: self.common,
             text: self.text,
             on_click: Some(Arc::new(f))
         }
     }
 }
+impl<St> CommonProperties for ButtonControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
 impl<St> HandleEvent<St> for ButtonControl<St> {
     fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
         match event {
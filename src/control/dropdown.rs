@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::fmt;
+
+use event::{Event, EventHandler, HandleEvent};
+use error;
+use control::Control;
+use props::{CommonProps, CommonProperties};
+
+/// Dropdown selecting one of a list of options. Requires a front-end renderer for `dropdown`
+/// controls (see FRONTEND.md).
+#[derive(Clone, Serialize)]
+pub struct DropdownControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    label: Option<String>,
+    options: Vec<String>,
+    pub curr_value: usize,
+    #[serde(skip)]
+    on_change: Option<Arc<EventHandler<DropdownChange, St>>>
+}
+impl<St> fmt::Debug for DropdownControl<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("DropdownControl")
+            .field("common", &self.common)
+            .field("label", &self.label)
+            .field("options", &self.options)
+            .field("curr_value", &self.curr_value)
+            .field("on_change", &match self.on_change {
+                Some(_) => "<<on_change handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> DropdownControl<St> {
+    pub fn new<S: AsRef<str>>(options: Vec<S>, curr_value: usize) -> DropdownControl<St> {
+        DropdownControl {
+            common: CommonProps::default(),
+            label: None,
+            options: options.iter().map(|s| s.as_ref().into()).collect(),
+            curr_value: curr_value,
+            on_change: None
+        }
+    }
+    pub fn with_label<S: AsRef<str>>(self, label: S) -> DropdownControl<St> {
+        DropdownControl {
+            label: Some(label.as_ref().into()),
+            ..self
+        }
+    }
+    pub fn with_on_change<E: 'static + EventHandler<DropdownChange, St>>(self, f: E)
+        -> DropdownControl<St>
+    {
+        DropdownControl {
+            on_change: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for DropdownControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for DropdownControl<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::DropdownChange(change_details) => {
+                match self.on_change {
+                    Some(ref handler) => handler.handle(change_details, state),
+                    None => Ok(state)
+                }
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "DropdownControl".into()
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DropdownChange {
+    pub idx: usize
+}
+
+impl<St> From<DropdownControl<St>> for Control<St> {
+    fn from(dropdown: DropdownControl<St>) -> Control<St> { Control::Dropdown(dropdown) }
+}
@@ -0,0 +1,68 @@
+use event::{Event, HandleEvent};
+use error;
+use control::Control;
+use history::HistoryAction;
+use props::{CommonProps, CommonProperties};
+
+/// Button undoing or redoing the most recent change to the dashboard state. Has no effect unless
+/// history is enabled for the app (see `RhubarbApp::history`).
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryControl {
+    #[serde(flatten)]
+    common: CommonProps,
+    label: String,
+    action: HistoryAction,
+}
+impl HistoryControl {
+    pub fn undo() -> HistoryControl {
+        HistoryControl {
+            common: CommonProps::default(),
+            label: "Undo".into(),
+            action: HistoryAction::Undo,
+        }
+    }
+    pub fn redo() -> HistoryControl {
+        HistoryControl {
+            common: CommonProps::default(),
+            label: "Redo".into(),
+            action: HistoryAction::Redo,
+        }
+    }
+    pub fn with_label<S: AsRef<str>>(self, label: S) -> HistoryControl {
+        HistoryControl {
+            label: label.as_ref().into(),
+            ..self
+        }
+    }
+    pub fn action(&self) -> HistoryAction {
+        self.action
+    }
+}
+impl CommonProperties for HistoryControl {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for HistoryControl {
+    // the state is restored by the graph handler, from the history of the client's session
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match (self.action, event) {
+            (HistoryAction::Undo, Event::Undo(_)) | (HistoryAction::Redo, Event::Redo(_)) =>
+                Ok(state),
+            (_, event) => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "HistoryControl".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<HistoryControl> for Control<St> {
+    fn from(control: HistoryControl) -> Control<St> { Control::History(control) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Undo {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Redo {}
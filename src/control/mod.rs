@@ -3,14 +3,26 @@ use serde::ser::SerializeStruct;
 
 pub mod button;
 pub mod slider;
+pub mod dropdown;
+pub mod text_input;
+pub mod view;
+pub mod history;
+pub mod timer;
 
 use event::{Event, HandleEvent};
 use error;
+use props::{CommonProps, CommonProperties};
 
 #[derive(Debug, Clone)]
 pub enum Control<St> {
     Button(button::ButtonControl<St>),
     Slider(slider::SliderControl<St>),
+    Dropdown(dropdown::DropdownControl<St>),
+    TextInput(text_input::TextInputControl<St>),
+    SaveView(view::SaveViewControl<St>),
+    LoadView(view::LoadViewControl<St>),
+    History(history::HistoryControl),
+    Timer(timer::TimerControl<St>),
 }
 impl<St> Serialize for Control<St> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
@@ -25,10 +37,60 @@ impl<St> Serialize for Control<St> {
                 state.serialize_field("type", "slider")?;
                 state.serialize_field("properties", slider)?;
             }
+            Control::Dropdown(ref dropdown) => {
+                state.serialize_field("type", "dropdown")?;
+                state.serialize_field("properties", dropdown)?;
+            }
+            Control::TextInput(ref input) => {
+                state.serialize_field("type", "text_input")?;
+                state.serialize_field("properties", input)?;
+            }
+            Control::SaveView(ref save) => {
+                state.serialize_field("type", "save_view")?;
+                state.serialize_field("properties", save)?;
+            }
+            Control::LoadView(ref load) => {
+                state.serialize_field("type", "load_view")?;
+                state.serialize_field("properties", load)?;
+            }
+            Control::History(ref history) => {
+                state.serialize_field("type", "history")?;
+                state.serialize_field("properties", history)?;
+            }
+            Control::Timer(ref timer) => {
+                state.serialize_field("type", "timer")?;
+                state.serialize_field("properties", timer)?;
+            }
         }
         state.end()
     }
 }
+impl<St> CommonProperties for Control<St> {
+    fn common(&self) -> &CommonProps {
+        match *self {
+            Control::Button(ref btn) => btn.common(),
+            Control::Slider(ref slider) => slider.common(),
+            Control::Dropdown(ref dropdown) => dropdown.common(),
+            Control::TextInput(ref input) => input.common(),
+            Control::SaveView(ref save) => save.common(),
+            Control::LoadView(ref load) => load.common(),
+            Control::History(ref history) => history.common(),
+            Control::Timer(ref timer) => timer.common(),
+        }
+    }
+    fn common_mut(&mut self) -> &mut CommonProps {
+        match *self {
+            Control::Button(ref mut btn) => btn.common_mut(),
+            Control::Slider(ref mut slider) => slider.common_mut(),
+            Control::Dropdown(ref mut dropdown) => dropdown.common_mut(),
+            Control::TextInput(ref mut input) => input.common_mut(),
+            Control::SaveView(ref mut save) => save.common_mut(),
+            Control::LoadView(ref mut load) => load.common_mut(),
+            Control::History(ref mut history) => history.common_mut(),
+            Control::Timer(ref mut timer) => timer.common_mut(),
+        }
+    }
+}
 impl<St> HandleEvent<St> for Control<St> {
     fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
         match *self {
@@ -37,6 +99,24 @@ impl<St> HandleEvent<St> for Control<St> {
             },
             Control::Slider(ref slider) => {
                 slider.handle_event(event, state)
+            },
+            Control::Dropdown(ref dropdown) => {
+                dropdown.handle_event(event, state)
+            },
+            Control::TextInput(ref input) => {
+                input.handle_event(event, state)
+            },
+            Control::SaveView(ref save) => {
+                save.handle_event(event, state)
+            },
+            Control::LoadView(ref load) => {
+                load.handle_event(event, state)
+            },
+            Control::History(ref history) => {
+                history.handle_event(event, state)
+            },
+            Control::Timer(ref timer) => {
+                timer.handle_event(event, state)
             }
         }
     }
@@ -4,9 +4,12 @@ use std::fmt;
 use event::{Event, EventHandler, HandleEvent};
 use error;
 use control::Control;
+use props::{CommonProps, CommonProperties};
 
 #[derive(Clone, Serialize)]
 pub struct SliderControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
     values: Vec<String>,
     pub curr_value: usize,
     #[serde(skip)]
@@ -15,6 +18,7 @@ pub struct SliderControl<St> {
 impl<St> fmt::Debug for SliderControl<St> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("SliderControl")
+            .field("common", &self.common)
             .field("values", &self.values)
             .field("curr_value", &self.curr_value)
             .field("on_change", &match self.on_change {
@@ -27,6 +31,7 @@ impl<St> fmt::Debug for SliderControl<St> {
 impl<St> SliderControl<St> {
     pub fn new<S: AsRef<str>>(values: Vec<S>, curr_value: usize) -> SliderControl<St> {
         SliderControl {
+            common: CommonProps::default(),
             values: values.iter().map(|s| s.as_ref().into()).collect(),
             curr_value: curr_value,
             on_change: None
@@ -36,12 +41,17 @@ impl<St> SliderControl<St> {
         -> SliderControl<St>
     {
         SliderControl {
+            common: self.common,
             values: self.values,
             curr_value: self.curr_value,
             on_change: Some(Arc::new(f))
         }
     }
 }
+impl<St> CommonProperties for SliderControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
 impl<St> HandleEvent<St> for SliderControl<St> {
     fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
         match event {
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::fmt;
+
+use event::{Event, EventHandler, HandleEvent};
+use error;
+use control::Control;
+use props::{CommonProps, CommonProperties};
+
+/// Single-line text input. Requires a front-end renderer for `text_input` controls (see
+/// FRONTEND.md).
+#[derive(Clone, Serialize)]
+pub struct TextInputControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    label: Option<String>,
+    placeholder: Option<String>,
+    pub value: String,
+    #[serde(skip)]
+    on_change: Option<Arc<EventHandler<TextChange, St>>>
+}
+impl<St> fmt::Debug for TextInputControl<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TextInputControl")
+            .field("common", &self.common)
+            .field("label", &self.label)
+            .field("placeholder", &self.placeholder)
+            .field("value", &self.value)
+            .field("on_change", &match self.on_change {
+                Some(_) => "<<on_change handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> TextInputControl<St> {
+    pub fn new<S: AsRef<str>>(value: S) -> TextInputControl<St> {
+        TextInputControl {
+            common: CommonProps::default(),
+            label: None,
+            placeholder: None,
+            value: value.as_ref().into(),
+            on_change: None
+        }
+    }
+    pub fn with_label<S: AsRef<str>>(self, label: S) -> TextInputControl<St> {
+        TextInputControl {
+            label: Some(label.as_ref().into()),
+            ..self
+        }
+    }
+    pub fn with_placeholder<S: AsRef<str>>(self, placeholder: S) -> TextInputControl<St> {
+        TextInputControl {
+            placeholder: Some(placeholder.as_ref().into()),
+            ..self
+        }
+    }
+    pub fn with_on_change<E: 'static + EventHandler<TextChange, St>>(self, f: E)
+        -> TextInputControl<St>
+    {
+        TextInputControl {
+            on_change: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for TextInputControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for TextInputControl<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::TextChange(change_details) => {
+                match self.on_change {
+                    Some(ref handler) => handler.handle(change_details, state),
+                    None => Ok(state)
+                }
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "TextInputControl".into()
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextChange {
+    pub value: String
+}
+
+impl<St> From<TextInputControl<St>> for Control<St> {
+    fn from(input: TextInputControl<St>) -> Control<St> { Control::TextInput(input) }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::fmt;
+
+use event::{Event, EventHandler, HandleEvent};
+use error;
+use control::Control;
+use props::{CommonProps, CommonProperties};
+
+/// Non-visual control which sends a `Tick` event every `interval_ms` milliseconds while it is
+/// running. Ticks are sent by the client like any other event, so timers work with any transport.
+/// Timers are started and stopped by updates (see `Update::set_timer_running`).
+#[derive(Clone, Serialize)]
+pub struct TimerControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    interval_ms: u64,
+    pub running: bool,
+    /// Number of ticks after which the timer stops by itself, if any.
+    max_ticks: Option<u64>,
+    /// Ticks received since the timer was last started (see `Layout::sync_event`).
+    #[serde(skip)]
+    ticks: u64,
+    #[serde(skip)]
+    on_tick: Option<Arc<EventHandler<Tick, St>>>
+}
+impl<St> fmt::Debug for TimerControl<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("TimerControl")
+            .field("common", &self.common)
+            .field("interval_ms", &self.interval_ms)
+            .field("running", &self.running)
+            .field("max_ticks", &self.max_ticks)
+            .field("ticks", &self.ticks)
+            .field("on_tick", &match self.on_tick {
+                Some(_) => "<<on_tick handler>>",
+                None => "none"
+            }.to_string())
+            .finish()
+    }
+}
+impl<St> TimerControl<St> {
+    /// New (stopped) timer ticking every `interval_ms` milliseconds once started.
+    pub fn new(interval_ms: u64) -> TimerControl<St> {
+        TimerControl {
+            common: CommonProps::default(),
+            interval_ms,
+            running: false,
+            max_ticks: None,
+            ticks: 0,
+            on_tick: None
+        }
+    }
+    pub fn with_running(self, running: bool) -> TimerControl<St> {
+        TimerControl { running, ..self }
+    }
+    pub fn with_interval(self, interval_ms: u64) -> TimerControl<St> {
+        TimerControl { interval_ms, ..self }
+    }
+    pub fn with_max_ticks(self, max_ticks: u64) -> TimerControl<St> {
+        TimerControl { max_ticks: Some(max_ticks), ..self }
+    }
+    pub fn with_on_tick<E: 'static + EventHandler<Tick, St>>(self, f: E) -> TimerControl<St> {
+        TimerControl {
+            on_tick: Some(Arc::new(f)),
+            ..self
+        }
+    }
+    pub fn interval(&self) -> u64 {
+        self.interval_ms
+    }
+    pub fn set_interval(&mut self, interval_ms: u64) {
+        self.interval_ms = interval_ms;
+    }
+    /// Start or stop the timer. Starting a stopped timer restarts its tick count.
+    pub fn set_running(&mut self, running: bool) {
+        if running && !self.running {
+            self.ticks = 0;
+        }
+        self.running = running;
+    }
+    /// Record a tick received from the client, stopping the timer once it has ticked `max_ticks`
+    /// times. Returns the number of ticks received since the timer was started.
+    pub fn record_tick(&mut self) -> u64 {
+        if self.running {
+            self.ticks += 1;
+            if self.max_ticks.map_or(false, |max_ticks| self.ticks >= max_ticks) {
+                self.running = false;
+            }
+        }
+        self.ticks
+    }
+}
+impl<St> CommonProperties for TimerControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for TimerControl<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::Tick(tick_details) => {
+                match self.on_tick {
+                    Some(ref handler) => handler.handle(tick_details, state),
+                    None => Ok(state)
+                }
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "TimerControl".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<TimerControl<St>> for Control<St> {
+    fn from(timer: TimerControl<St>) -> Control<St> { Control::Timer(timer) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tick {
+    /// Number of ticks sent since the timer was (re)started, starting at 1. For clients with a
+    /// session, this is counted by the server rather than trusted from the client.
+    pub count: u64
+}
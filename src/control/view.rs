@@ -0,0 +1,170 @@
+use std::sync::Arc;
+use std::fmt;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use event::{Event, HandleEvent};
+use error;
+use control::Control;
+use props::{CommonProps, CommonProperties};
+use store::{ViewStore, save_view, load_view};
+
+/// Control saving the current dashboard state to a view store, under a name entered by the user.
+#[derive(Clone, Serialize)]
+pub struct SaveViewControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    label: Option<String>,
+    placeholder: Option<String>,
+    #[serde(skip)]
+    store: Arc<ViewStore>,
+    #[serde(skip)]
+    save: fn(&ViewStore, &str, &St) -> error::Result<()>,
+}
+impl<St> fmt::Debug for SaveViewControl<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SaveViewControl")
+            .field("common", &self.common)
+            .field("label", &self.label)
+            .field("placeholder", &self.placeholder)
+            .field("store", &"<<view store>>")
+            .finish()
+    }
+}
+impl<St: Serialize> SaveViewControl<St> {
+    pub fn new(store: Arc<ViewStore>) -> SaveViewControl<St> {
+        SaveViewControl {
+            common: CommonProps::default(),
+            label: None,
+            placeholder: None,
+            store,
+            save: save_view::<St>,
+        }
+    }
+}
+impl<St> SaveViewControl<St> {
+    pub fn with_label<S: AsRef<str>>(self, label: S) -> SaveViewControl<St> {
+        SaveViewControl {
+            label: Some(label.as_ref().into()),
+            ..self
+        }
+    }
+    pub fn with_placeholder<S: AsRef<str>>(self, placeholder: S) -> SaveViewControl<St> {
+        SaveViewControl {
+            placeholder: Some(placeholder.as_ref().into()),
+            ..self
+        }
+    }
+}
+impl<St> CommonProperties for SaveViewControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for SaveViewControl<St> {
+    fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
+        match event {
+            Event::SaveView(save_details) => {
+                (self.save)(&*self.store, &save_details.name, &state)?;
+                Ok(state)
+            }
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "SaveViewControl".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<SaveViewControl<St>> for Control<St> {
+    fn from(control: SaveViewControl<St>) -> Control<St> { Control::SaveView(control) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveView {
+    pub name: String
+}
+
+/// Control restoring a dashboard state from the views saved in a view store. The list of saved
+/// views is read from the store when the control is created; to show views saved since, include
+/// a refreshed copy of this control in an update (see `refresh` and `Update::add_component`).
+#[derive(Clone, Serialize)]
+pub struct LoadViewControl<St> {
+    #[serde(flatten)]
+    common: CommonProps,
+    label: Option<String>,
+    views: Vec<String>,
+    #[serde(skip)]
+    store: Arc<ViewStore>,
+    #[serde(skip)]
+    load: fn(&ViewStore, &str) -> error::Result<St>,
+}
+impl<St> fmt::Debug for LoadViewControl<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("LoadViewControl")
+            .field("common", &self.common)
+            .field("label", &self.label)
+            .field("views", &self.views)
+            .field("store", &"<<view store>>")
+            .finish()
+    }
+}
+impl<St: DeserializeOwned> LoadViewControl<St> {
+    pub fn new(store: Arc<ViewStore>) -> LoadViewControl<St> {
+        // a failing store shouldn't prevent the dashboard from being built
+        let views = store.names().unwrap_or_else(|e| {
+            warn!("unable to list saved views: {}", e);
+            vec![]
+        });
+        LoadViewControl {
+            common: CommonProps::default(),
+            label: None,
+            views,
+            store,
+            load: load_view::<St>,
+        }
+    }
+}
+impl<St> LoadViewControl<St> {
+    pub fn with_label<S: AsRef<str>>(self, label: S) -> LoadViewControl<St> {
+        LoadViewControl {
+            label: Some(label.as_ref().into()),
+            ..self
+        }
+    }
+    /// Names of the saved views offered by this control.
+    pub fn views(&self) -> &[String] {
+        &self.views
+    }
+    /// Re-read the names of the saved views from the store.
+    pub fn refresh(&mut self) -> error::Result<()> {
+        self.views = self.store.names()?;
+        Ok(())
+    }
+}
+impl<St> CommonProperties for LoadViewControl<St> {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
+}
+impl<St> HandleEvent<St> for LoadViewControl<St> {
+    fn handle_event(&self, event: Event, _state: St) -> error::Result<St> {
+        match event {
+            Event::LoadView(load_details) => (self.load)(&*self.store, &load_details.name),
+            _ => {
+                return Err(error::RhubarbError::InvalidEvent {
+                    event,
+                    component_type: "LoadViewControl".into()
+                })
+            }
+        }
+    }
+}
+impl<St> From<LoadViewControl<St>> for Control<St> {
+    fn from(control: LoadViewControl<St>) -> Control<St> { Control::LoadView(control) }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoadView {
+    pub name: String
+}
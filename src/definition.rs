@@ -0,0 +1,623 @@
+//! Declarative dashboard definitions.
+//!
+//! A dashboard's panels and controls can be described in a TOML (with the `toml` feature) or YAML
+//! (with the `yaml` feature) file and loaded into a `Layout`, so cosmetic changes don't require
+//! recompilation. Event handlers are referenced by name and bound from a `HandlerRegistry`.
+//!
+//! An example TOML definition:
+//!
+//! ```toml
+//! [[panels]]
+//! name = "main"
+//!
+//!   [[panels.controls]]
+//!   type = "slider"
+//!   name = "year"
+//!   values = ["1987", "2006"]
+//!   on_change = "set_year"
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+
+use control::Control;
+use control::button::{ButtonControl, ButtonClick};
+use control::slider::{SliderControl, SliderChange};
+use control::dropdown::{DropdownControl, DropdownChange};
+use control::timer::{TimerControl, Tick};
+use control::text_input::{TextInputControl, TextChange};
+use event::EventHandler;
+use error::{self, RhubarbError};
+use layout::{ComponentIndex, Layout, Panel};
+use props::{Align, PanelKind};
+
+/// Top-level dashboard definition.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayoutDef {
+    #[serde(default)]
+    pub panels: Vec<PanelDef>,
+    /// Source lines of the definition's values, if it was parsed from a TOML or YAML source.
+    #[serde(skip)]
+    pub lines: SourceLines,
+}
+
+/// Lines (1-based) of the values in a definition source, by key path (e.g.
+/// `panels[0].controls[1].name`). Positions are reported by the TOML or YAML parser.
+#[derive(Debug, Clone, Default)]
+pub struct SourceLines(HashMap<String, usize>);
+impl SourceLines {
+    /// Line of the value at `path`, if known.
+    pub fn line(&self, path: &str) -> Option<usize> {
+        self.0.get(path).cloned()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PanelDef {
+    pub name: String,
+    #[serde(default)]
+    pub kind: PanelKind,
+    pub gap: Option<u32>,
+    pub align: Option<Align>,
+    pub justify: Option<Align>,
+    #[serde(default)]
+    pub wrap: bool,
+    #[serde(default)]
+    pub panels: Vec<PanelDef>,
+    #[serde(default)]
+    pub controls: Vec<ControlDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ControlDef {
+    Button {
+        name: String,
+        text: String,
+        on_click: Option<String>,
+    },
+    Slider {
+        name: String,
+        values: Vec<String>,
+        #[serde(default)]
+        value: usize,
+        on_change: Option<String>,
+    },
+    Dropdown {
+        name: String,
+        label: Option<String>,
+        options: Vec<String>,
+        #[serde(default)]
+        value: usize,
+        on_change: Option<String>,
+    },
+    #[serde(rename = "text_input")]
+    TextInput {
+        name: String,
+        label: Option<String>,
+        placeholder: Option<String>,
+        #[serde(default)]
+        value: String,
+        on_change: Option<String>,
+    },
+    Timer {
+        name: String,
+        interval_ms: u64,
+        #[serde(default)]
+        running: bool,
+        max_ticks: Option<u64>,
+        on_tick: Option<String>,
+    },
+}
+impl ControlDef {
+    pub fn name(&self) -> &str {
+        match *self {
+            ControlDef::Button { ref name, .. } => name,
+            ControlDef::Slider { ref name, .. } => name,
+            ControlDef::Dropdown { ref name, .. } => name,
+            ControlDef::TextInput { ref name, .. } => name,
+            ControlDef::Timer { ref name, .. } => name,
+        }
+    }
+    /// The handler key and handler name referenced by this control, if any.
+    fn handler(&self) -> Option<(&'static str, &str)> {
+        match *self {
+            ControlDef::Button { ref on_click, .. } =>
+                on_click.as_ref().map(|h| ("on_click", h.as_str())),
+            ControlDef::Slider { ref on_change, .. }
+                | ControlDef::Dropdown { ref on_change, .. }
+                | ControlDef::TextInput { ref on_change, .. } =>
+                on_change.as_ref().map(|h| ("on_change", h.as_str())),
+            ControlDef::Timer { ref on_tick, .. } =>
+                on_tick.as_ref().map(|h| ("on_tick", h.as_str())),
+        }
+    }
+}
+
+/// A problem found while loading a dashboard definition.
+#[derive(Debug, Clone)]
+pub struct DefinitionError {
+    /// Line (1-based) of the definition source the problem was found on, if known.
+    pub line: Option<usize>,
+    pub message: String,
+}
+impl DefinitionError {
+    fn new(message: String) -> DefinitionError {
+        DefinitionError { line: None, message }
+    }
+}
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Named event handlers which controls in a dashboard definition can refer to.
+pub struct HandlerRegistry<St> {
+    button: HashMap<String, Arc<EventHandler<ButtonClick, St>>>,
+    slider: HashMap<String, Arc<EventHandler<SliderChange, St>>>,
+    dropdown: HashMap<String, Arc<EventHandler<DropdownChange, St>>>,
+    text_input: HashMap<String, Arc<EventHandler<TextChange, St>>>,
+    timer: HashMap<String, Arc<EventHandler<Tick, St>>>,
+}
+impl<St> Default for HandlerRegistry<St> {
+    fn default() -> HandlerRegistry<St> {
+        HandlerRegistry {
+            button: HashMap::new(),
+            slider: HashMap::new(),
+            dropdown: HashMap::new(),
+            text_input: HashMap::new(),
+            timer: HashMap::new(),
+        }
+    }
+}
+impl<St> fmt::Debug for HandlerRegistry<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("HandlerRegistry")
+            .field("button", &self.button.keys().collect::<Vec<_>>())
+            .field("slider", &self.slider.keys().collect::<Vec<_>>())
+            .field("dropdown", &self.dropdown.keys().collect::<Vec<_>>())
+            .field("text_input", &self.text_input.keys().collect::<Vec<_>>())
+            .field("timer", &self.timer.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+impl<St: 'static> HandlerRegistry<St> {
+    pub fn new() -> HandlerRegistry<St> { HandlerRegistry::default() }
+
+    /// Register a button click handler under `name`.
+    pub fn on_click<S: AsRef<str>, E: 'static + EventHandler<ButtonClick, St>>(mut self, name: S,
+        f: E) -> HandlerRegistry<St>
+    {
+        self.button.insert(name.as_ref().into(), Arc::new(f));
+        self
+    }
+    /// Register a slider change handler under `name`.
+    pub fn on_change<S: AsRef<str>, E: 'static + EventHandler<SliderChange, St>>(mut self,
+        name: S, f: E) -> HandlerRegistry<St>
+    {
+        self.slider.insert(name.as_ref().into(), Arc::new(f));
+        self
+    }
+    /// Register a dropdown change handler under `name`.
+    pub fn on_dropdown_change<S: AsRef<str>, E: 'static + EventHandler<DropdownChange, St>>(
+        mut self, name: S, f: E) -> HandlerRegistry<St>
+    {
+        self.dropdown.insert(name.as_ref().into(), Arc::new(f));
+        self
+    }
+    /// Register a text input change handler under `name`.
+    pub fn on_text_change<S: AsRef<str>, E: 'static + EventHandler<TextChange, St>>(mut self,
+        name: S, f: E) -> HandlerRegistry<St>
+    {
+        self.text_input.insert(name.as_ref().into(), Arc::new(f));
+        self
+    }
+    /// Register a timer tick handler under `name`.
+    pub fn on_tick<S: AsRef<str>, E: 'static + EventHandler<Tick, St>>(mut self, name: S, f: E)
+        -> HandlerRegistry<St>
+    {
+        self.timer.insert(name.as_ref().into(), Arc::new(f));
+        self
+    }
+
+    fn contains(&self, def: &ControlDef, handler: &str) -> bool {
+        match *def {
+            ControlDef::Button { .. } => self.button.contains_key(handler),
+            ControlDef::Slider { .. } => self.slider.contains_key(handler),
+            ControlDef::Dropdown { .. } => self.dropdown.contains_key(handler),
+            ControlDef::TextInput { .. } => self.text_input.contains_key(handler),
+            ControlDef::Timer { .. } => self.timer.contains_key(handler),
+        }
+    }
+
+    fn build(&self, def: &ControlDef) -> Control<St> {
+        match *def {
+            ControlDef::Button { ref text, ref on_click, .. } => {
+                let button = ButtonControl::new(text);
+                Control::Button(match on_click.as_ref().and_then(|h| self.button.get(h)) {
+                    Some(handler) => {
+                        let handler = handler.clone();
+                        button.with_on_click(move |click: ButtonClick, state: St| {
+                            handler.handle(click, state)
+                        })
+                    },
+                    None => button
+                })
+            },
+            ControlDef::Slider { ref values, value, ref on_change, .. } => {
+                let slider = SliderControl::new(values.clone(), value);
+                Control::Slider(match on_change.as_ref().and_then(|h| self.slider.get(h)) {
+                    Some(handler) => {
+                        let handler = handler.clone();
+                        slider.with_on_change(move |change: SliderChange, state: St| {
+                            handler.handle(change, state)
+                        })
+                    },
+                    None => slider
+                })
+            },
+            ControlDef::Dropdown { ref label, ref options, value, ref on_change, .. } => {
+                let mut dropdown = DropdownControl::new(options.clone(), value);
+                if let Some(ref label) = *label {
+                    dropdown = dropdown.with_label(label);
+                }
+                Control::Dropdown(match on_change.as_ref().and_then(|h| self.dropdown.get(h)) {
+                    Some(handler) => {
+                        let handler = handler.clone();
+                        dropdown.with_on_change(move |change: DropdownChange, state: St| {
+                            handler.handle(change, state)
+                        })
+                    },
+                    None => dropdown
+                })
+            },
+            ControlDef::TextInput { ref label, ref placeholder, ref value, ref on_change, .. } => {
+                let mut input = TextInputControl::new(value);
+                if let Some(ref label) = *label {
+                    input = input.with_label(label);
+                }
+                if let Some(ref placeholder) = *placeholder {
+                    input = input.with_placeholder(placeholder);
+                }
+                Control::TextInput(match on_change.as_ref().and_then(|h| self.text_input.get(h)) {
+                    Some(handler) => {
+                        let handler = handler.clone();
+                        input.with_on_change(move |change: TextChange, state: St| {
+                            handler.handle(change, state)
+                        })
+                    },
+                    None => input
+                })
+            },
+            ControlDef::Timer { interval_ms, running, max_ticks, ref on_tick, .. } => {
+                let mut timer = TimerControl::new(interval_ms).with_running(running);
+                if let Some(max_ticks) = max_ticks {
+                    timer = timer.with_max_ticks(max_ticks);
+                }
+                Control::Timer(match on_tick.as_ref().and_then(|h| self.timer.get(h)) {
+                    Some(handler) => {
+                        let handler = handler.clone();
+                        timer.with_on_tick(move |tick: Tick, state: St| {
+                            handler.handle(tick, state)
+                        })
+                    },
+                    None => timer
+                })
+            }
+        }
+    }
+}
+
+impl LayoutDef {
+    /// Parse a TOML dashboard definition.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(source: &str) -> error::Result<LayoutDef> {
+        let parse_err = |e: ::toml::de::Error| {
+            let line = e.line_col().map(|(line, _)| line + 1);
+            RhubarbError::Definition(vec![DefinitionError { line, message: e.to_string() }])
+        };
+        let mut def: LayoutDef = ::toml::from_str(source).map_err(&parse_err)?;
+        let located: toml_lines::LocatedLayout = ::toml::from_str(source).map_err(&parse_err)?;
+        def.lines = located.lines(source);
+        Ok(def)
+    }
+    /// Parse a YAML dashboard definition.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(source: &str) -> error::Result<LayoutDef> {
+        let mut def: LayoutDef = ::serde_yaml::from_str(source).map_err(|e| {
+            let line = e.location().map(|loc| loc.line());
+            RhubarbError::Definition(vec![DefinitionError { line, message: e.to_string() }])
+        })?;
+        def.lines = yaml_lines::lines(source)?;
+        Ok(def)
+    }
+
+    /// Check this definition for duplicate component names (including names already registered
+    /// in `layout`) and references to unregistered handlers.
+    pub fn validate<St: 'static>(&self, layout: &Layout<St>, handlers: &HandlerRegistry<St>)
+        -> Vec<DefinitionError>
+    {
+        let mut validator = Validator { seen: HashSet::new(), errors: vec![], lines: &self.lines };
+        for (i, panel) in self.panels.iter().enumerate() {
+            validator.panel(panel, &format!("panels[{}]", i), None, layout, handlers);
+        }
+        validator.errors
+    }
+
+    /// Add the panels and controls in this definition to `layout`. The definition should be
+    /// validated first (see `validate`).
+    pub fn build_into<St: 'static>(&self, layout: &mut Layout<St>,
+        handlers: &HandlerRegistry<St>) -> error::Result<()>
+    {
+        for panel in &self.panels {
+            add_panel(panel, None, layout, handlers)?;
+        }
+        Ok(())
+    }
+
+    /// Validate this definition and build it into a new `Layout`.
+    pub fn into_layout<St: 'static>(&self, handlers: &HandlerRegistry<St>)
+        -> error::Result<Layout<St>>
+    {
+        let mut layout = Layout::new();
+        let errors = self.validate(&layout, handlers);
+        if !errors.is_empty() {
+            return Err(RhubarbError::Definition(errors));
+        }
+        self.build_into(&mut layout, handlers)?;
+        Ok(layout)
+    }
+}
+
+fn add_panel<St: 'static>(def: &PanelDef, parent: Option<ComponentIndex>,
+    layout: &mut Layout<St>, handlers: &HandlerRegistry<St>) -> error::Result<()>
+{
+    let mut panel = Panel::with_kind(def.kind).with_wrap(def.wrap);
+    if let Some(gap) = def.gap {
+        panel = panel.with_gap(gap);
+    }
+    if let Some(align) = def.align {
+        panel = panel.with_align(align);
+    }
+    if let Some(justify) = def.justify {
+        panel = panel.with_justify(justify);
+    }
+    let panel_idx = layout.add_panel_with(&def.name, panel, parent)?;
+    for control in &def.controls {
+        layout.add_control_to_panel(control.name(), handlers.build(control), panel_idx)?;
+    }
+    for panel in &def.panels {
+        add_panel(panel, Some(panel_idx), layout, handlers)?;
+    }
+    Ok(())
+}
+
+struct Validator<'a> {
+    seen: HashSet<String>,
+    errors: Vec<DefinitionError>,
+    lines: &'a SourceLines,
+}
+impl<'a> Validator<'a> {
+    fn panel<St: 'static>(&mut self, def: &PanelDef, path: &str, parent: Option<&str>,
+        layout: &Layout<St>, handlers: &HandlerRegistry<St>)
+    {
+        self.name(&def.name, path, "panel", parent, layout);
+        for (i, control) in def.controls.iter().enumerate() {
+            let control_path = format!("{}.controls[{}]", path, i);
+            self.name(control.name(), &control_path, "control", Some(&def.name), layout);
+            if let Some((key, handler)) = control.handler() {
+                if !handlers.contains(control, handler) {
+                    let line = self.lines.line(&format!("{}.{}", control_path, key));
+                    self.errors.push(DefinitionError { line, message: format!(
+                        "missing {} handler '{}' for control '{}' in panel '{}'", key, handler,
+                        control.name(), def.name) });
+                }
+            }
+        }
+        for (i, panel) in def.panels.iter().enumerate() {
+            self.panel(panel, &format!("{}.panels[{}]", path, i), Some(&def.name), layout,
+                handlers);
+        }
+    }
+    fn name<St>(&mut self, name: &str, path: &str, kind: &str, parent: Option<&str>,
+        layout: &Layout<St>)
+    {
+        if !self.seen.insert(name.to_string()) || layout.get_component_index(name).is_some() {
+            let line = self.lines.line(&format!("{}.name", path));
+            self.errors.push(DefinitionError { line, message: match parent {
+                Some(parent) => format!("duplicate component name '{}' ({} in panel '{}')",
+                    name, kind, parent),
+                None => format!("duplicate component name '{}' (top-level {})", name, kind),
+            } });
+        }
+    }
+}
+
+/// Source lines of TOML definitions, from the spans the TOML parser reports for the values which
+/// are checked during validation.
+#[cfg(feature = "toml")]
+mod toml_lines {
+    use std::collections::HashMap;
+
+    use toml::Spanned;
+
+    use super::SourceLines;
+
+    #[derive(Deserialize)]
+    pub struct LocatedLayout {
+        #[serde(default)]
+        panels: Vec<LocatedPanel>,
+    }
+    #[derive(Deserialize)]
+    struct LocatedPanel {
+        name: Spanned<String>,
+        #[serde(default)]
+        panels: Vec<LocatedPanel>,
+        #[serde(default)]
+        controls: Vec<LocatedControl>,
+    }
+    #[derive(Deserialize)]
+    struct LocatedControl {
+        name: Spanned<String>,
+        on_click: Option<Spanned<String>>,
+        on_change: Option<Spanned<String>>,
+        on_tick: Option<Spanned<String>>,
+    }
+
+    impl LocatedLayout {
+        pub fn lines(&self, source: &str) -> SourceLines {
+            let mut lines = HashMap::new();
+            for (i, panel) in self.panels.iter().enumerate() {
+                panel.add_lines(&format!("panels[{}]", i), source, &mut lines);
+            }
+            SourceLines(lines)
+        }
+    }
+    impl LocatedPanel {
+        fn add_lines(&self, path: &str, source: &str, lines: &mut HashMap<String, usize>) {
+            lines.insert(format!("{}.name", path), line(source, &self.name));
+            for (i, control) in self.controls.iter().enumerate() {
+                let path = format!("{}.controls[{}]", path, i);
+                lines.insert(format!("{}.name", path), line(source, &control.name));
+                let handlers = [("on_click", &control.on_click), ("on_change", &control.on_change),
+                    ("on_tick", &control.on_tick)];
+                for &(key, handler) in &handlers {
+                    if let Some(ref handler) = *handler {
+                        lines.insert(format!("{}.{}", path, key), line(source, handler));
+                    }
+                }
+            }
+            for (i, panel) in self.panels.iter().enumerate() {
+                panel.add_lines(&format!("{}.panels[{}]", path, i), source, lines);
+            }
+        }
+    }
+
+    fn line(source: &str, value: &Spanned<String>) -> usize {
+        source[..value.start()].matches('\n').count() + 1
+    }
+}
+
+/// Source lines of YAML definitions, from the positions of the YAML parser's events.
+#[cfg(feature = "yaml")]
+mod yaml_lines {
+    use std::collections::HashMap;
+
+    use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+    use yaml_rust::scanner::Marker;
+
+    use error::{self, RhubarbError};
+    use super::{DefinitionError, SourceLines};
+
+    enum Frame {
+        // key of the value being parsed, or `None` while parsing a key
+        Mapping(Option<String>),
+        // index of the item being parsed
+        Sequence(usize),
+    }
+
+    #[derive(Default)]
+    struct Receiver {
+        stack: Vec<Frame>,
+        lines: HashMap<String, usize>,
+    }
+    impl Receiver {
+        fn path(&self) -> String {
+            let mut path = String::new();
+            for frame in &self.stack {
+                match *frame {
+                    Frame::Mapping(Some(ref key)) => {
+                        if !path.is_empty() {
+                            path.push('.');
+                        }
+                        path.push_str(key);
+                    },
+                    Frame::Mapping(None) => {},
+                    Frame::Sequence(idx) => path.push_str(&format!("[{}]", idx)),
+                }
+            }
+            path
+        }
+        fn parsing_key(&self) -> bool {
+            match self.stack.last() {
+                Some(&Frame::Mapping(None)) => true,
+                _ => false,
+            }
+        }
+        // move on to the next key or item of the innermost mapping or sequence
+        fn value_done(&mut self) {
+            match self.stack.last_mut() {
+                Some(&mut Frame::Mapping(ref mut key)) => *key = None,
+                Some(&mut Frame::Sequence(ref mut idx)) => *idx += 1,
+                None => {},
+            }
+        }
+    }
+    impl MarkedEventReceiver for Receiver {
+        fn on_event(&mut self, event: Event, mark: Marker) {
+            match event {
+                Event::Scalar(value, ..) => {
+                    if self.parsing_key() {
+                        self.stack.pop();
+                        self.stack.push(Frame::Mapping(Some(value)));
+                    } else {
+                        let path = self.path();
+                        self.lines.entry(path).or_insert(mark.line());
+                        self.value_done();
+                    }
+                },
+                Event::Alias(..) => self.value_done(),
+                Event::MappingStart(..) => self.stack.push(Frame::Mapping(None)),
+                Event::SequenceStart(..) => self.stack.push(Frame::Sequence(0)),
+                Event::MappingEnd | Event::SequenceEnd => {
+                    self.stack.pop();
+                    self.value_done();
+                },
+                _ => {},
+            }
+        }
+    }
+
+    pub fn lines(source: &str) -> error::Result<SourceLines> {
+        let mut receiver = Receiver::default();
+        Parser::new(source.chars()).load(&mut receiver, false).map_err(|e| {
+            RhubarbError::Definition(vec![DefinitionError {
+                line: Some(e.marker().line()),
+                message: e.to_string(),
+            }])
+        })?;
+        Ok(SourceLines(receiver.lines))
+    }
+}
+
+/// Load a dashboard definition file into a new `Layout`, binding handlers from `handlers`. The
+/// file format is chosen by extension (`.toml`, or `.yaml` / `.yml`).
+pub fn load_layout<St: 'static, P: AsRef<Path>>(path: P, handlers: &HandlerRegistry<St>)
+    -> error::Result<Layout<St>>
+{
+    let path = path.as_ref();
+    let mut source = String::new();
+    File::open(path).and_then(|mut f| f.read_to_string(&mut source)).map_err(|err| {
+        RhubarbError::Definition(vec![DefinitionError::new(format!(
+            "unable to read dashboard definition {}: {}", path.display(), err))])
+    })?;
+    let def = match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "toml")]
+        Some("toml") => LayoutDef::from_toml_str(&source)?,
+        #[cfg(feature = "yaml")]
+        Some("yaml") | Some("yml") => LayoutDef::from_yaml_str(&source)?,
+        _ => {
+            return Err(RhubarbError::Definition(vec![DefinitionError::new(format!(
+                "unsupported dashboard definition format: {}", path.display()))]));
+        }
+    };
+    def.into_layout(handlers)
+}
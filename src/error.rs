@@ -1,11 +1,16 @@
 use std::error::Error;
 use std::fmt;
+use std::io;
+use std::path::PathBuf;
 
 use serde_json;
-use handlebars::{TemplateFileError, RenderError};
+use handlebars::{TemplateError, TemplateFileError, RenderError};
 use agnes::error::AgnesError;
+use rhubarb_graph::error::GraphError;
 
 use event::Event;
+use layout::ComponentIndex;
+use definition::DefinitionError;
 
 /// Generate Rhubarb error enum.
 #[derive(Debug)]
@@ -14,12 +19,39 @@ pub enum RhubarbError {
     DataFrame(AgnesError),
     /// Serde JSON error
     Json(serde_json::Error),
+    /// Graph rendering or conversion error
+    Graph(GraphError),
     /// Template (Handlebars) Error
     Template(Box<Error>),
+    /// Unable to load a required asset file
+    MissingAsset {
+        path: PathBuf,
+        err: io::Error,
+    },
     /// Missing key in the component registry
     ComponentRegistry(String),
     /// Invalid call adding a component to a layout
     InvalidLayout(String),
+    /// Component index not present in the layout
+    UnknownComponent(ComponentIndex),
+    /// Component name already registered in the layout
+    DuplicateComponent(String),
+    /// Event received for a disabled component
+    DisabledComponent(ComponentIndex),
+    /// Invalid dashboard definition file
+    Definition(Vec<DefinitionError>),
+    /// Invalid page of a multi-page app
+    InvalidPage(String),
+    /// Shared (URL-encoded) state which could not be decoded
+    InvalidState(String),
+    /// Saved view which does not exist or could not be restored
+    InvalidView(String),
+    /// Failure reading from or writing to a view store
+    ViewStore(String),
+    /// Event received for an unknown or expired client session
+    InvalidSession(String),
+    /// Unable to export a static HTML document
+    Export(String),
     /// Invalid event for specified component
     InvalidEvent {
         event: Event,
@@ -27,6 +59,64 @@ pub enum RhubarbError {
     }
 }
 
+impl RhubarbError {
+    /// Short, machine-readable name of this error's kind.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            RhubarbError::DataFrame(_) => "DataFrame",
+            RhubarbError::Json(_) => "Json",
+            RhubarbError::Graph(_) => "Graph",
+            RhubarbError::Template(_) => "Template",
+            RhubarbError::MissingAsset { .. } => "MissingAsset",
+            RhubarbError::ComponentRegistry(_) => "ComponentRegistry",
+            RhubarbError::InvalidLayout(_) => "InvalidLayout",
+            RhubarbError::UnknownComponent(_) => "UnknownComponent",
+            RhubarbError::DuplicateComponent(_) => "DuplicateComponent",
+            RhubarbError::DisabledComponent(_) => "DisabledComponent",
+            RhubarbError::Definition(_) => "Definition",
+            RhubarbError::InvalidPage(_) => "InvalidPage",
+            RhubarbError::InvalidState(_) => "InvalidState",
+            RhubarbError::InvalidView(_) => "InvalidView",
+            RhubarbError::ViewStore(_) => "ViewStore",
+            RhubarbError::InvalidSession(_) => "InvalidSession",
+            RhubarbError::Export(_) => "Export",
+            RhubarbError::InvalidEvent { .. } => "InvalidEvent",
+        }
+    }
+
+    /// Index of the component this error refers to, if any.
+    pub fn component_index(&self) -> Option<ComponentIndex> {
+        match *self {
+            RhubarbError::UnknownComponent(idx) => Some(idx),
+            RhubarbError::DisabledComponent(idx) => Some(idx),
+            _ => None
+        }
+    }
+}
+
+/// Error payload sent to the client when a request could not be handled.
+#[derive(Debug, Serialize)]
+pub struct ErrorMessage {
+    pub kind: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<ComponentIndex>,
+}
+impl ErrorMessage {
+    pub fn new(err: &RhubarbError, component: Option<ComponentIndex>) -> ErrorMessage {
+        ErrorMessage {
+            kind: err.kind().into(),
+            message: err.to_string(),
+            component: component.or(err.component_index()),
+        }
+    }
+}
+impl<'a> From<&'a RhubarbError> for ErrorMessage {
+    fn from(err: &'a RhubarbError) -> ErrorMessage {
+        ErrorMessage::new(err, None)
+    }
+}
+
 /// Wrapper for Rhubarb-based results.
 pub type Result<T> = ::std::result::Result<T, RhubarbError>;
 
@@ -35,11 +125,35 @@ impl fmt::Display for RhubarbError {
         match *self {
             RhubarbError::DataFrame(ref err) => write!(f, "DataFrame error: {}", err),
             RhubarbError::Json(ref err) => write!(f, "JSON error: {}", err),
+            RhubarbError::Graph(ref err) => write!(f, "Graph error: {}", err),
             RhubarbError::Template(ref err) => write!(f, "Templating error: {}", err),
+            RhubarbError::MissingAsset { ref path, ref err } => write!(f,
+                "Unable to load asset '{}': {}", path.display(), err),
             RhubarbError::ComponentRegistry(ref s) => write!(f,
                 "Missing component name in component registry: {}", s),
             RhubarbError::InvalidLayout(ref s) => write!(f,
                 "Invalid layout: {}", s),
+            RhubarbError::UnknownComponent(idx) => write!(f,
+                "Unknown component index: {}", idx),
+            RhubarbError::DuplicateComponent(ref name) => write!(f,
+                "Component name already in use: {}", name),
+            RhubarbError::DisabledComponent(idx) => write!(f,
+                "Event received for disabled component: {}", idx),
+            RhubarbError::Definition(ref errors) => write!(f,
+                "Invalid dashboard definition: {}",
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ")),
+            RhubarbError::InvalidPage(ref s) => write!(f,
+                "Invalid page: {}", s),
+            RhubarbError::InvalidState(ref s) => write!(f,
+                "Invalid shared state: {}", s),
+            RhubarbError::InvalidView(ref s) => write!(f,
+                "Invalid view: {}", s),
+            RhubarbError::ViewStore(ref s) => write!(f,
+                "View store error: {}", s),
+            RhubarbError::InvalidSession(ref s) => write!(f,
+                "Invalid session: {}", s),
+            RhubarbError::Export(ref s) => write!(f,
+                "Export error: {}", s),
             RhubarbError::InvalidEvent { ref event, ref component_type } => write!(f,
                 "Invalid event '{}' for component '{}'", event.name(), component_type),
         }
@@ -51,9 +165,21 @@ impl Error for RhubarbError {
         match *self {
             RhubarbError::DataFrame(ref err) => err.description(),
             RhubarbError::Json(ref err) => err.description(),
+            RhubarbError::Graph(ref err) => err.description(),
             RhubarbError::Template(ref err) => err.description(),
+            RhubarbError::MissingAsset { .. } => "missing asset",
             RhubarbError::ComponentRegistry(_) => "missing component",
             RhubarbError::InvalidLayout(_) => "invalid layout",
+            RhubarbError::UnknownComponent(_) => "unknown component",
+            RhubarbError::DuplicateComponent(_) => "duplicate component name",
+            RhubarbError::DisabledComponent(_) => "disabled component",
+            RhubarbError::Definition(_) => "invalid dashboard definition",
+            RhubarbError::InvalidPage(_) => "invalid page",
+            RhubarbError::InvalidState(_) => "invalid shared state",
+            RhubarbError::InvalidView(_) => "invalid view",
+            RhubarbError::ViewStore(_) => "view store error",
+            RhubarbError::InvalidSession(_) => "invalid session",
+            RhubarbError::Export(_) => "export error",
             RhubarbError::InvalidEvent { .. } => "invalid event for component"
         }
     }
@@ -62,9 +188,21 @@ impl Error for RhubarbError {
         match *self {
             RhubarbError::DataFrame(ref err) => Some(err),
             RhubarbError::Json(ref err) => Some(err),
+            RhubarbError::Graph(ref err) => Some(err),
             RhubarbError::Template(ref err) => Some(err.as_ref()),
+            RhubarbError::MissingAsset { ref err, .. } => Some(err),
             RhubarbError::ComponentRegistry(_) => None,
             RhubarbError::InvalidLayout(_) => None,
+            RhubarbError::UnknownComponent(_) => None,
+            RhubarbError::DuplicateComponent(_) => None,
+            RhubarbError::DisabledComponent(_) => None,
+            RhubarbError::Definition(_) => None,
+            RhubarbError::InvalidPage(_) => None,
+            RhubarbError::InvalidState(_) => None,
+            RhubarbError::InvalidView(_) => None,
+            RhubarbError::ViewStore(_) => None,
+            RhubarbError::InvalidSession(_) => None,
+            RhubarbError::Export(_) => None,
             RhubarbError::InvalidEvent { .. } => None,
         }
     }
@@ -80,6 +218,16 @@ impl From<serde_json::Error> for RhubarbError {
         RhubarbError::Json(err)
     }
 }
+impl From<GraphError> for RhubarbError {
+    fn from(err: GraphError) -> RhubarbError {
+        RhubarbError::Graph(err)
+    }
+}
+impl From<TemplateError> for RhubarbError {
+    fn from(err: TemplateError) -> RhubarbError {
+        RhubarbError::Template(Box::new(err))
+    }
+}
 impl From<TemplateFileError> for RhubarbError {
     fn from(err: TemplateFileError) -> RhubarbError {
         RhubarbError::Template(Box::new(err))
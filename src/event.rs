@@ -1,8 +1,11 @@
 use std::panic::RefUnwindSafe;
 
 use control;
+use container;
+use chart;
 use error;
 use layout::ComponentIndex;
+use history::HistoryAction;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EventMessage {
@@ -14,13 +17,43 @@ pub struct EventMessage {
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Event {
     ButtonClick(control::button::ButtonClick),
-    SliderChange(control::slider::SliderChange)
+    SliderChange(control::slider::SliderChange),
+    DropdownChange(control::dropdown::DropdownChange),
+    TextChange(control::text_input::TextChange),
+    SaveView(control::view::SaveView),
+    LoadView(control::view::LoadView),
+    Undo(control::history::Undo),
+    Redo(control::history::Redo),
+    Tick(control::timer::Tick),
+    TabChange(container::TabChange),
+    ModalClose(container::ModalClose),
+    ChartClick(chart::ChartClick),
+    ChartSelect(chart::ChartSelect),
 }
 impl Event {
     pub fn name(&self) -> &str {
         match *self {
             Event::ButtonClick(_) => "ButtonClick",
             Event::SliderChange(_) => "SliderChange",
+            Event::DropdownChange(_) => "DropdownChange",
+            Event::TextChange(_) => "TextChange",
+            Event::SaveView(_) => "SaveView",
+            Event::LoadView(_) => "LoadView",
+            Event::Undo(_) => "Undo",
+            Event::Redo(_) => "Redo",
+            Event::Tick(_) => "Tick",
+            Event::TabChange(_) => "TabChange",
+            Event::ModalClose(_) => "ModalClose",
+            Event::ChartClick(_) => "ChartClick",
+            Event::ChartSelect(_) => "ChartSelect",
+        }
+    }
+    /// Direction to move through the state history, for undo and redo events.
+    pub fn history_action(&self) -> Option<HistoryAction> {
+        match *self {
+            Event::Undo(_) => Some(HistoryAction::Undo),
+            Event::Redo(_) => Some(HistoryAction::Redo),
+            _ => None,
         }
     }
 }
@@ -0,0 +1,100 @@
+//! Static HTML export of charts and dashboard snapshots.
+//!
+//! Exported documents are single files which can be opened without access to a running Rhubarb
+//! server or the internet: plotly.js and all chart data are inlined. Exporting therefore requires
+//! a local copy of plotly.js (see `PlotlySource::Local` and `RhubarbApp::offline_plotly`).
+
+use std::fs::File;
+use std::io::Read;
+
+use serde_json;
+
+use {Chart, ChartState};
+use error::{self, RhubarbError};
+use layout::Layout;
+use props::CommonProperties;
+use resource::{PageConfig, PlotlySource};
+use update::GenerateUpdate;
+
+/// Render a chart into a single HTML document with plotly.js and the chart data inlined.
+pub fn to_html(chart: &Chart, plotly: &PlotlySource) -> error::Result<String> {
+    Ok(format!(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\" />\n</head>\n<body>\n",
+        "{plotly}\n{chart}</body>\n</html>\n"),
+        plotly = plotly_script(plotly)?,
+        chart = chart_html("chart", chart)?
+    ))
+}
+
+/// Render the charts of a dashboard, as they would initially appear with the provided state,
+/// into a single HTML document with plotly.js and all data inlined. Charts are shown in the order
+/// of the initial update, each under its title (see `CommonProperties::with_title`) if it has
+/// one. Controls are left out, since they have no effect without a server.
+pub fn snapshot_html<St, Gen>(layout: &Layout<St>, gen: &Gen, state: St, page: &PageConfig)
+    -> error::Result<String>
+    where St: ChartState, Gen: GenerateUpdate<St>
+{
+    let mut layout = layout.clone();
+    let mut update = gen.update(&layout, None, state)?;
+    update.apply_layout_changes(&mut layout)?;
+    let mut charts = String::new();
+    if let Some(chart) = update.chart() {
+        charts.push_str(&chart_html("chart", chart)?);
+    }
+    for indexed in update.indexed_charts() {
+        if let Some(title) = layout.get(indexed.idx).and_then(|c| c.common().title.clone()) {
+            charts.push_str(&format!("<h2>{}</h2>\n", escape_html(&title)));
+        }
+        charts.push_str(&chart_html(&format!("chart-{}", indexed.idx), &indexed.chart)?);
+    }
+    Ok(format!(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\" />\n",
+        "<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n",
+        "{plotly}\n{charts}</body>\n</html>\n"),
+        title = escape_html(&page.title),
+        plotly = plotly_script(&page.plotly)?,
+        charts = charts
+    ))
+}
+
+/// Element showing `chart`, along with the script plotting it.
+fn chart_html(id: &str, chart: &Chart) -> error::Result<String> {
+    let figure = json_script_safe(&serde_json::to_string(chart)?);
+    Ok(format!(concat!(
+        "<div id=\"{id}\"></div>\n",
+        "<script>\n(function() {{\n  var figure = {figure};\n",
+        "  Plotly.newPlot('{id}', figure.data, figure.layout);\n}})();\n</script>\n"),
+        id = id,
+        figure = figure
+    ))
+}
+
+/// Inlined plotly.js. Exports must not depend on the Plotly CDN, so a local copy is required.
+fn plotly_script(plotly: &PlotlySource) -> error::Result<String> {
+    match *plotly {
+        PlotlySource::Local(ref path) => {
+            let mut js = String::new();
+            File::open(path).and_then(|mut f| f.read_to_string(&mut js)).map_err(|err| {
+                RhubarbError::MissingAsset { path: path.clone(), err }
+            })?;
+            Ok(format!("<script>\n{}\n</script>", script_safe(&js)))
+        },
+        PlotlySource::Cdn { .. } => Err(RhubarbError::Export(
+            "plotly.js must be inlined into static exports; provide a local copy with \
+            `PlotlySource::Local` (or `RhubarbApp::offline_plotly`)".into())),
+    }
+}
+
+/// Prevent inlined JSON from prematurely closing its `<script>` element.
+pub(crate) fn json_script_safe(s: &str) -> String {
+    s.replace("</", "<\\/")
+}
+
+/// Prevent inlined JavaScript from prematurely closing its `<script>` element.
+fn script_safe(s: &str) -> String {
+    s.replace("</script", "<\\/script").replace("</SCRIPT", "<\\/SCRIPT")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -2,46 +2,104 @@ use std::marker::PhantomData;
 use std::fs::File;
 use std::io::{self, Read};
 use std::sync::Arc;
+use std::time::Instant;
 
 use mime;
 use gotham::http::response::create_response;
 use gotham::state::{State, FromState};
 use gotham::handler::{Handler, NewHandler, HandlerFuture, IntoHandlerFuture, IntoHandlerError};
-use hyper::{Response, StatusCode, Body};
-use hyper::header::{AccessControlAllowOrigin, AccessControlAllowHeaders};
+use hyper::{Response, StatusCode, Body, Headers, Uri};
+use hyper::header::{AccessControlAllowOrigin, CacheControl, CacheDirective, ETag, EntityTag,
+    IfNoneMatch};
 use futures::{future, Future, Stream};
-use handlebars::{Handlebars, to_json};
+use handlebars::{Handlebars, TemplateFileError, to_json};
 use serde_json::value::{Map};
 use serde_json;
-use unicase::Ascii;
 
-use resource::{default_scripts, default_styles};
-use logger::log_handler_err;
-use update::{LayoutUpdate, GenerateUpdate, ClientMessage};
-use layout::Layout;
+use resource::{PageConfig, TemplateSource, NavLink, FrontendConfig, GRAPH_URL};
+use assets::{Asset, AssetKind, AssetSource};
+use logger::{log_handler_err, RequestSpan};
+use update::{Update, LayoutUpdate, GenerateUpdate, ClientMessage};
+use layout::{Layout, ComponentIndex};
+use error::{self, RhubarbError, ErrorMessage};
+use session::{self, Session, Sessions};
+use share;
+use export::json_script_safe;
 use ChartState;
 
+// stands in for the front-end configuration in rendered pages, which depends on the request
+const CONFIG_PLACEHOLDER: &str = "__RHUBARB_CONFIG__";
+
 #[derive(Clone, Debug)]
 pub struct NewIndexHandler {
     page: String,
+    config: FrontendConfig,
 }
 impl NewIndexHandler {
-    pub fn new() -> NewIndexHandler {
+    pub fn new(config: &PageConfig, assets: &AssetSource) -> error::Result<NewIndexHandler> {
+        NewIndexHandler::for_page(config, assets, GRAPH_URL, &[])
+    }
+    /// Index handler of a dashboard whose graph endpoint is at `graph_url`, with links to the
+    /// other pages of the app in `nav`. URLs are relative to the app root.
+    pub fn for_page(config: &PageConfig, assets: &AssetSource, graph_url: &str, nav: &[NavLink])
+        -> error::Result<NewIndexHandler>
+    {
         let mut hbs = Handlebars::new();
-        let template_file = "./templates/base.hbs";
-        hbs.register_template_file("base", template_file)
-            .expect(&format!("unrecoverable error: unable to register template file {}",
-                template_file));
+        match config.template {
+            TemplateSource::Default => {
+                let template = assets.load_string(AssetKind::BaseTemplate).map_err(|e| {
+                    TemplateFileError::IOError(e, AssetKind::BaseTemplate.path().into())
+                })?;
+                hbs.register_template_string("base", template)?
+            },
+            TemplateSource::File(ref path) => hbs.register_template_file("base", path)?,
+            TemplateSource::Inline(ref template) => hbs.register_template_string("base",
+                template)?,
+        }
+        for &(ref name, ref source) in &config.partials {
+            match *source {
+                TemplateSource::File(ref path) => {
+                    let mut partial = String::new();
+                    File::open(path).and_then(|mut f| f.read_to_string(&mut partial))
+                        .map_err(|e| TemplateFileError::IOError(e, name.clone()))?;
+                    hbs.register_partial(name, partial)?;
+                },
+                TemplateSource::Inline(ref partial) => hbs.register_partial(name, partial)?,
+            }
+        }
 
         let mut data = Map::new();
-        data.insert("title".to_string(), to_json(&"Graph!".to_string()));
-        data.insert("scripts".to_string(), to_json(&default_scripts()));
-        data.insert("styles".to_string(), to_json(&default_styles()));
+        data.insert("title".to_string(), to_json(&config.title));
+        data.insert("scripts".to_string(), to_json(&config.all_scripts()));
+        data.insert("styles".to_string(), to_json(&config.all_styles()));
+        data.insert("meta".to_string(), to_json(&config.meta));
+        data.insert("favicon".to_string(), to_json(&config.favicon));
+        data.insert("base_url".to_string(), to_json(&config.base_url));
+        data.insert("graph_url".to_string(), to_json(&graph_url));
+        data.insert("nav".to_string(), to_json(&nav));
+        data.insert("config".to_string(), to_json(&CONFIG_PLACEHOLDER));
 
-        let page = hbs.render("base", &data)
-            .expect(&format!("unrecoverable error: unable to render template file {}",
-                template_file));
-        NewIndexHandler { page: page }
+        let page = hbs.render("base", &data)?;
+        let config = FrontendConfig {
+            base_url: config.base_url.clone(),
+            graph_url: graph_url.into(),
+            state: None,
+        };
+        Ok(NewIndexHandler { page, config })
+    }
+    /// Render the page for a request with the query string `query`. Shared state in the query
+    /// (see `share`) is passed to the front-end, both decoded and as part of the graph URL, so
+    /// the dashboard starts from that state. The state is only checked to be valid JSON here;
+    /// the graph endpoint rejects states which don't match the dashboard's state type.
+    pub fn render(&self, query: Option<&str>) -> error::Result<String> {
+        let mut config = self.config.clone();
+        if let Some(encoded) = query.and_then(share::state_param) {
+            config.state = Some(share::decode_state::<serde_json::Value>(encoded)?);
+            config.graph_url = format!("{}?{}={}", config.graph_url, share::STATE_PARAM,
+                encoded);
+        }
+        let config = json_script_safe(&serde_json::to_string(&config)?);
+        Ok(self.page.replacen(CONFIG_PLACEHOLDER, &config, 1))
     }
 }
 
@@ -49,33 +107,34 @@ impl NewHandler for NewIndexHandler {
     type Instance = IndexHandler;
 
     fn new_handler(&self) -> io::Result<IndexHandler> {
-        Ok(IndexHandler::new(self.page.clone()))
+        Ok(IndexHandler::new(self.clone()))
     }
 }
 
 pub struct IndexHandler {
-    page: String,
+    index: NewIndexHandler,
 }
 impl IndexHandler {
-    pub fn new(page: String) -> IndexHandler {
-        IndexHandler { page: page }
+    pub fn new(index: NewIndexHandler) -> IndexHandler {
+        IndexHandler { index }
     }
 }
 impl Handler for IndexHandler {
     fn handle(self, state: State) -> Box<HandlerFuture> {
-        let res = create_response(
-            &state,
-            StatusCode::Ok,
-            Some((self.page.into_bytes(), mime::TEXT_HTML)),
-        );
+        let res = match self.index.render(Uri::borrow_from(&state).query()) {
+            Ok(page) => create_response(&state, StatusCode::Ok,
+                Some((page.into_bytes(), mime::TEXT_HTML))),
+            Err(e) => {
+                log_handler_err("index", &e);
+                create_response(&state, error_status(&e),
+                    Some((e.to_string().into_bytes(), mime::TEXT_PLAIN)))
+            }
+        };
         (state, res).into_handler_future()
     }
 }
 
 macro_rules! serve_file {
-    ($handler_name:ident, $file_name:expr, $mime_type:expr) => {
-        serve_file!(_inner $handler_name, $file_name, $mime_type, |_| {});
-    };
     (_inner $handler_name:ident, $file_name:expr, $mime_type:expr, $res_manip:expr) => {
 
 pub fn $handler_name(state: State) -> (State, Response) {
@@ -114,14 +173,98 @@ macro_rules! serve_file_to_anyone {
     };
 }
 
-serve_file!(app_bundle_js, "./assets/bundle.js", mime::TEXT_JAVASCRIPT);
-serve_file!(app_bundle_css, "./assets/bundle.css", mime::TEXT_CSS);
-serve_file_to_anyone!(test_json, "./assets/test.json", mime::APPLICATION_JSON);
+serve_file_to_anyone!(test_json, concat!(env!("CARGO_MANIFEST_DIR"), "/assets/test.json"),
+    mime::APPLICATION_JSON);
+
+#[derive(Clone, Debug)]
+enum ServedAsset {
+    /// Asset loaded once, when the handler is created.
+    Preloaded(Arc<Asset>),
+    /// Asset reloaded from its source on every request.
+    Reloaded(AssetSource, AssetKind),
+}
+
+#[derive(Clone, Debug)]
+pub struct NewAssetHandler {
+    asset: ServedAsset,
+}
+impl NewAssetHandler {
+    pub fn new(source: AssetSource, kind: AssetKind) -> NewAssetHandler {
+        // static assets never change, so only load them once
+        let asset = if source.is_static() {
+            match source.load(kind) {
+                Ok(asset) => ServedAsset::Preloaded(Arc::new(asset)),
+                Err(_) => ServedAsset::Reloaded(source, kind),
+            }
+        } else {
+            ServedAsset::Reloaded(source, kind)
+        };
+        NewAssetHandler { asset }
+    }
+    /// Serve an asset which has already been loaded.
+    pub fn preloaded(asset: Asset) -> NewAssetHandler {
+        NewAssetHandler { asset: ServedAsset::Preloaded(Arc::new(asset)) }
+    }
+}
+
+impl NewHandler for NewAssetHandler {
+    type Instance = AssetHandler;
+
+    fn new_handler(&self) -> io::Result<AssetHandler> {
+        Ok(AssetHandler { asset: self.asset.clone() })
+    }
+}
+
+pub struct AssetHandler {
+    asset: ServedAsset,
+}
+impl Handler for AssetHandler {
+    fn handle(self, state: State) -> Box<HandlerFuture> {
+        let res = match self.asset {
+            ServedAsset::Preloaded(ref asset) => asset_response(&state, asset, true),
+            ServedAsset::Reloaded(ref source, kind) => match source.load(kind) {
+                Ok(asset) => asset_response(&state, &asset, source.is_static()),
+                Err(e) => {
+                    log_handler_err(kind.path(), e);
+                    create_response(&state, StatusCode::NotFound, None)
+                }
+            }
+        };
+        (state, res).into_handler_future()
+    }
+}
+
+fn asset_response(state: &State, asset: &Asset, is_static: bool) -> Response {
+    let etag = EntityTag::strong(asset.etag.clone());
+    let not_modified = match Headers::borrow_from(state).get::<IfNoneMatch>() {
+        Some(&IfNoneMatch::Any) => true,
+        Some(&IfNoneMatch::Items(ref tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    let mut res = if not_modified {
+        create_response(state, StatusCode::NotModified, None)
+    } else {
+        create_response(state, StatusCode::Ok,
+            Some((asset.contents.to_vec(), asset.mime.clone())))
+    };
+    {
+        let headers = res.headers_mut();
+        headers.set(ETag(etag));
+        headers.set(CacheControl(if is_static {
+            vec![CacheDirective::Public, CacheDirective::MaxAge(3600)]
+        } else {
+            vec![CacheDirective::NoCache]
+        }));
+    }
+    res
+}
 
 #[derive(Clone, Debug)]
 pub struct NewGraphHandler<St, Gen: GenerateUpdate<St>> {
     graph_gen: Gen,
     layout: Arc<Layout<St>>,
+    sessions: Arc<Sessions<St>>,
+    history_depth: Option<usize>,
     phantom: PhantomData<St>
 }
 impl<St, Gen: GenerateUpdate<St>> NewGraphHandler<St, Gen> {
@@ -129,9 +272,18 @@ impl<St, Gen: GenerateUpdate<St>> NewGraphHandler<St, Gen> {
         NewGraphHandler {
             graph_gen: gen,
             layout,
+            sessions: Arc::new(Sessions::new()),
+            history_depth: None,
             phantom: PhantomData
         }
     }
+    /// Keep a history of up to `depth` states for each session, enabling undo and redo.
+    pub fn with_history(self, depth: usize) -> NewGraphHandler<St, Gen> {
+        NewGraphHandler {
+            history_depth: Some(depth),
+            ..self
+        }
+    }
 }
 
 impl<St, Gen> NewHandler for NewGraphHandler<St, Gen>
@@ -140,112 +292,266 @@ impl<St, Gen> NewHandler for NewGraphHandler<St, Gen>
     type Instance = GraphHandler<St, Gen>;
 
     fn new_handler(&self) -> io::Result<GraphHandler<St, Gen>> {
-        Ok(GraphHandler::new(self.graph_gen.clone(), self.layout.clone()))
+        let handler = GraphHandler::new(self.graph_gen.clone(), self.layout.clone(),
+            self.sessions.clone());
+        Ok(match self.history_depth {
+            Some(depth) => handler.with_history(depth),
+            None => handler,
+        })
     }
 }
 
 pub struct GraphHandler<St, Gen: GenerateUpdate<St>> {
     updater: Gen,
     layout: Arc<Layout<St>>,
+    sessions: Arc<Sessions<St>>,
+    history_depth: Option<usize>,
     phantom: PhantomData<St>
 }
 impl<St, Gen: GenerateUpdate<St>> GraphHandler<St, Gen> {
-    pub fn new(gen: Gen, layout: Arc<Layout<St>>) -> GraphHandler<St, Gen> {
+    /// Handler serving updates for `layout`, with the sessions of its clients kept in `sessions`.
+    pub fn new(gen: Gen, layout: Arc<Layout<St>>, sessions: Arc<Sessions<St>>)
+        -> GraphHandler<St, Gen>
+    {
         GraphHandler {
             updater: gen,
             layout: layout,
+            sessions,
+            history_depth: None,
             phantom: PhantomData
         }
     }
+    /// Keep a history of up to `depth` states for each new session, enabling undo and redo.
+    pub fn with_history(self, depth: usize) -> GraphHandler<St, Gen> {
+        GraphHandler {
+            history_depth: Some(depth),
+            ..self
+        }
+    }
+}
+
+impl<St, Gen> GraphHandler<St, Gen>
+    where St: ChartState, Gen: GenerateUpdate<St> + 'static
+{
+    fn respond(&self, body: &[u8], query: Option<&str>, span: &mut RequestSpan)
+        -> ::std::result::Result<Vec<u8>, RequestError>
+    {
+        if body.len() == 0 {
+            return self.start_session(query, span);
+        }
+        let ClientMessage { ui_state, mut event_message, session }: ClientMessage<St> =
+            serde_json::from_slice(body).map_err(|e| RequestError {
+                status: StatusCode::BadRequest,
+                error: e.into(),
+                component: None,
+            })?;
+        let component_idx = event_message.idx;
+        span.set_component(component_idx);
+        span.set_event(event_message.event.name());
+        debug!("handling event '{}' for component {}", event_message.event.name(),
+            component_idx);
+        let prev_state = ui_state.clone();
+        let history_action = event_message.event.history_action();
+
+        let token = match session {
+            Some(token) => token,
+            None => {
+                // clients without a session see the layout the app was created with, which
+                // can't be changed
+                let new_state = self.layout.handle_event(event_message, ui_state)
+                    .map_err(|e| RequestError::new(e, Some(component_idx)))?;
+                let update = self.generate(&self.layout, Some(prev_state), new_state, span)?;
+                if update.modifies_layout() {
+                    return Err(RequestError::new(RhubarbError::InvalidSession(
+                        "layout changes can only be sent to clients with a session".into()),
+                        None));
+                }
+                return serde_json::to_vec(&update).map_err(|e| RequestError::new(e.into(), None));
+            }
+        };
+        let session = self.sessions.get(&token).map_err(|e| RequestError::new(e, None))?;
+        // hold the session until it has been updated
+        let mut session = session::lock(&session);
+        session.layout.sync_event(&mut event_message);
+        let new_state = session.layout.handle_event(event_message, ui_state)
+            .map_err(|e| RequestError::new(e, Some(component_idx)))?;
+        // restore the state for undo and redo events, and record it for all others; the session's
+        // history is only replaced once the update has been generated
+        let mut history = session.history.clone();
+        let new_state = match (history.as_mut(), history_action) {
+            (Some(history), Some(action)) => history.step(action, new_state),
+            (Some(history), None) => {
+                history.record_change(prev_state.clone(), &new_state)
+                    .map_err(|e| RequestError::new(e, None))?;
+                new_state
+            },
+            (None, _) => new_state,
+        };
+        let mut update = self.generate(&session.layout, Some(prev_state), new_state, span)?;
+        update.apply_layout_changes(&mut session.layout).map_err(|e| RequestError::new(e, None))?;
+        if let Some(ref history) = history {
+            update.set_history(history.status());
+        }
+        session.history = history;
+        update.set_session(token);
+        serde_json::to_vec(&update).map_err(|e| RequestError::new(e.into(), None))
+    }
+
+    /// Respond to a client's initial request with the layout and the update for the initial
+    /// (shared or default) state, starting a new session.
+    fn start_session(&self, query: Option<&str>, span: &mut RequestSpan)
+        -> ::std::result::Result<Vec<u8>, RequestError>
+    {
+        let ui_state = share::initial_state(query).map_err(|e| RequestError::new(e, None))?;
+        let mut layout = Layout::clone(&self.layout);
+        let mut update = self.generate(&layout, None, ui_state, span)?;
+        update.apply_layout_changes(&mut layout).map_err(|e| RequestError::new(e, None))?;
+        let token = session::new_token();
+        let session = Session::new(layout, self.history_depth);
+        if let Some(ref history) = session.history {
+            update.set_history(history.status());
+        }
+        update.set_session(token.clone());
+        let bytes = serde_json::to_vec(&LayoutUpdate::new(update, &session.layout))
+            .map_err(|e| RequestError::new(e.into(), None))?;
+        self.sessions.insert(token, session);
+        Ok(bytes)
+    }
+
+    /// Call the provided update generator.
+    fn generate(&self, layout: &Layout<St>, prev_state: Option<St>, state: St,
+        span: &mut RequestSpan) -> ::std::result::Result<Update<St>, RequestError>
+    {
+        let update_start = Instant::now();
+        let update = self.updater.update(layout, prev_state, state)
+            .map_err(|e| RequestError::new(e, None))?;
+        span.set_update_latency(update_start.elapsed());
+        Ok(update)
+    }
 }
 
 impl<St, Gen> Handler for GraphHandler<St, Gen>
     where St: ChartState, Gen: GenerateUpdate<St> + 'static
 {
     fn handle(self, mut state: State) -> Box<HandlerFuture> {
+        let mut span = RequestSpan::new(&state);
+        let query = Uri::borrow_from(&state).query().map(|query| query.to_string());
         let future = Body::take_from(&mut state)
             .concat2()
             .then(move |full_body| {
-
-                // parse the body into the UI state object (or default if unparsed)
-                let body_content = match full_body {
-                    Ok(valid_body) => {
-                        String::from_utf8(valid_body.to_vec()).unwrap()
-                    },
-                    Err(e) => return future::err((state, e.into_handler_error())),
-                };
-                let (prev_ui_state, ui_state): (Option<St>, St) = if body_content.len() == 0 {
-                    (None, St::default())
-                } else {
-                    match serde_json::from_str(&body_content) {
-                        Ok(cm) => {
-                            let ClientMessage { ui_state, event_message }: ClientMessage<St> = cm;
-                            let prev_state = ui_state.clone();
-                            let new_state = match self.layout.handle_event(event_message,
-                                ui_state)
-                            {
-                                Ok(new_state) => new_state,
-                                Err(e) => {
-                                    return future::err((state, e.into_handler_error()));
-                                }
-                            };
-                            (Some(prev_state), new_state)
-                        },
-                        Err(e) => {
-                            println!("failed deserialize: {}", e);
-                            return future::err((state, e.into_handler_error()));
-                        }
-                    }
-                };
-                let do_send_layout = prev_ui_state.is_none();
-
-                // call the provided container generator function
-                let update_result = self.updater.update(&self.layout, prev_ui_state, ui_state)
-                    .and_then(|update| {
-                        // add layout to (initial) update message if required, and serialize
-                        if do_send_layout {
-                            let update = LayoutUpdate::new(update, &self.layout);
-                            // println!("{}", serde_json::to_string_pretty(&update).unwrap());
-                            serde_json::to_vec(&update).map_err(|e| e.into())
-                        } else {
-                            // println!("{}", serde_json::to_string_pretty(&update).unwrap());
-                            serde_json::to_vec(&update).map_err(|e| e.into())
-                        }
-                    }
-                );
-                match update_result {
-                    Ok(bytes) => {
-                        let mut res = create_response(
-                            &state,
-                            StatusCode::Ok,
-                            Some((bytes, mime::APPLICATION_JSON))
-                        );
-                        //TODO: break this out into configurable header manipulation component
-                        res.headers_mut().set(AccessControlAllowOrigin::Value(
-                            "http://localhost:4200".into()));
-                        future::ok((state, res))
-                    },
+                let body = match full_body {
+                    Ok(valid_body) => valid_body,
                     Err(e) => {
-                        log_handler_err("graph", e);
-                        let res = create_response(
-                            &state,
-                            StatusCode::InternalServerError,
-                            None
-                        );
-                        future::ok((state, res))
+                        span.fail();
+                        return future::err((state, e.into_handler_error()));
                     }
-                }
+                };
+                let res = match self.respond(&body, query.as_ref().map(|q| q.as_str()),
+                    &mut span)
+                {
+                    Ok(bytes) => create_response(
+                        &state,
+                        StatusCode::Ok,
+                        Some((bytes, mime::APPLICATION_JSON))
+                    ),
+                    Err(e) => e.into_response(&state)
+                };
+                span.finish(res.status());
+                future::ok((state, res))
             });
         Box::new(future)
     }
 }
 
-pub fn options_origin(state: State) -> (State, Response) {
-    let mut res = create_response(&state, StatusCode::Ok, Some((vec![], mime::TEXT_PLAIN)));
-    {
-        let headers = res.headers_mut();
-        headers.set(AccessControlAllowOrigin::Value("http://localhost:4200".into()));
-        headers.set(AccessControlAllowHeaders(vec![Ascii::new("Content-Type".into())]));
+/// Failure while handling a graph request, along with the HTTP status to respond with.
+struct RequestError {
+    status: StatusCode,
+    error: RhubarbError,
+    component: Option<ComponentIndex>,
+}
+impl RequestError {
+    fn new(error: RhubarbError, component: Option<ComponentIndex>) -> RequestError {
+        let component = component.or(error.component_index());
+        RequestError { status: error_status(&error), error, component }
+    }
+    fn into_response(self, state: &State) -> Response {
+        log_handler_err("graph", &self.error);
+        let message = ErrorMessage::new(&self.error, self.component);
+        let body = serde_json::to_vec(&message).unwrap_or_default();
+        create_response(state, self.status, Some((body, mime::APPLICATION_JSON)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewSvgHandler<St, Gen: GenerateUpdate<St>> {
+    graph_gen: Gen,
+    layout: Arc<Layout<St>>,
+    phantom: PhantomData<St>
+}
+impl<St, Gen: GenerateUpdate<St>> NewSvgHandler<St, Gen> {
+    pub fn new(gen: Gen, layout: Arc<Layout<St>>) -> NewSvgHandler<St, Gen> {
+        NewSvgHandler {
+            graph_gen: gen,
+            layout,
+            phantom: PhantomData
+        }
+    }
+}
+
+impl<St, Gen> NewHandler for NewSvgHandler<St, Gen>
+    where St: ChartState, Gen: 'static + GenerateUpdate<St>
+{
+    type Instance = SvgHandler<St, Gen>;
+
+    fn new_handler(&self) -> io::Result<SvgHandler<St, Gen>> {
+        Ok(SvgHandler::new(self.graph_gen.clone(), self.layout.clone()))
+    }
+}
+
+/// Handler serving the current chart rendered as an SVG image.
+pub struct SvgHandler<St, Gen: GenerateUpdate<St>> {
+    updater: Gen,
+    layout: Arc<Layout<St>>,
+    phantom: PhantomData<St>
+}
+impl<St, Gen: GenerateUpdate<St>> SvgHandler<St, Gen> {
+    pub fn new(gen: Gen, layout: Arc<Layout<St>>) -> SvgHandler<St, Gen> {
+        SvgHandler {
+            updater: gen,
+            layout,
+            phantom: PhantomData
+        }
+    }
+}
+impl<St, Gen> Handler for SvgHandler<St, Gen>
+    where St: ChartState, Gen: GenerateUpdate<St> + 'static
+{
+    fn handle(self, state: State) -> Box<HandlerFuture> {
+        let query = Uri::borrow_from(&state).query();
+        let svg = share::initial_state(query)
+            .and_then(|ui_state| self.updater.update(&self.layout, None, ui_state))
+            .and_then(|update| {
+                update.charts().first().map(|chart| chart.to_svg().map(Some)).unwrap_or(Ok(None))
+            });
+        let res = match svg {
+            Ok(Some(svg)) => create_response(&state, StatusCode::Ok,
+                Some((svg.into_bytes(), mime::IMAGE_SVG))),
+            Ok(None) => create_response(&state, StatusCode::NotFound,
+                Some((b"no chart available".to_vec(), mime::TEXT_PLAIN))),
+            Err(e) => RequestError::new(e, None).into_response(&state),
+        };
+        (state, res).into_handler_future()
+    }
+}
+
+fn error_status(err: &RhubarbError) -> StatusCode {
+    match *err {
+        RhubarbError::UnknownComponent(_) => StatusCode::BadRequest,
+        RhubarbError::InvalidEvent { .. } => StatusCode::UnprocessableEntity,
+        RhubarbError::DisabledComponent(_) => StatusCode::UnprocessableEntity,
+        RhubarbError::InvalidState(_) => StatusCode::BadRequest,
+        RhubarbError::InvalidView(_) => StatusCode::UnprocessableEntity,
+        RhubarbError::InvalidSession(_) => StatusCode::BadRequest,
+        _ => StatusCode::InternalServerError,
     }
-    (state, res)
 }
@@ -0,0 +1,100 @@
+//! Per-session undo/redo history of dashboard state.
+//!
+//! When history is enabled (see `RhubarbApp::history`), each client session (see `session`)
+//! keeps a history of its states. The state before each event is recorded, and `Undo` / `Redo`
+//! events (sent by `HistoryControl`s) restore recorded states.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use serde_json;
+
+use error;
+
+/// Direction to move through a history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+/// Whether there are states to undo or redo, sent to the client with each update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+/// Bounded history of the states of a single session.
+#[derive(Debug, Clone)]
+pub struct History<St> {
+    depth: usize,
+    undo: VecDeque<St>,
+    redo: Vec<St>,
+}
+impl<St> History<St> {
+    /// New history remembering at most `depth` states to undo.
+    pub fn new(depth: usize) -> History<St> {
+        History {
+            depth,
+            undo: VecDeque::new(),
+            redo: vec![],
+        }
+    }
+    /// Record the state preceding a change. Clears any states which could have been redone.
+    pub fn record(&mut self, prev_state: St) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(prev_state);
+        self.redo.clear();
+    }
+    /// Move back to the most recently recorded state, if any.
+    pub fn undo(&mut self, state: St) -> Option<St> {
+        self.undo.pop_back().map(|prev_state| {
+            self.redo.push(state);
+            prev_state
+        })
+    }
+    /// Move forward to the most recently undone state, if any.
+    pub fn redo(&mut self, state: St) -> Option<St> {
+        self.redo.pop().map(|next_state| {
+            self.undo.push_back(state);
+            next_state
+        })
+    }
+    /// Record the state preceding an event, unless the event left the state unchanged.
+    pub fn record_change(&mut self, prev_state: St, state: &St) -> error::Result<()>
+        where St: Serialize
+    {
+        if serde_json::to_value(&prev_state)? != serde_json::to_value(state)? {
+            self.record(prev_state);
+        }
+        Ok(())
+    }
+    /// Undo or redo a change. Returns the restored state, or `state` if there is nothing to undo
+    /// or redo.
+    pub fn step(&mut self, action: HistoryAction, state: St) -> St
+        where St: Clone
+    {
+        let restored = match action {
+            HistoryAction::Undo => self.undo(state.clone()),
+            HistoryAction::Redo => self.redo(state.clone()),
+        };
+        restored.unwrap_or(state)
+    }
+    /// Number of states which can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+    pub fn status(&self) -> HistoryStatus {
+        HistoryStatus {
+            can_undo: !self.undo.is_empty(),
+            can_redo: !self.redo.is_empty(),
+        }
+    }
+}
@@ -6,6 +6,9 @@ use serde::ser::{SerializeStruct, SerializeSeq};
 
 use ChartState;
 use control::Control;
+use container::Container;
+use chart::ChartComponent;
+use props::{Align, CommonProps, CommonProperties, PanelKind};
 use event::{Event, EventMessage, HandleEvent};
 use error;
 
@@ -16,6 +19,43 @@ type ComponentRegistry = HashMap<String, ComponentIndex>;
 pub enum Component<St> {
     Control(Control<St>),
     Panel(Panel),
+    Container(Container<St>),
+    Chart(ChartComponent<St>),
+}
+impl<St> Component<St> {
+    /// Child components of panels and containers, or `None` for other components.
+    pub fn children(&self) -> Option<&[ComponentIndex]> {
+        match *self {
+            Component::Control(_) | Component::Chart(_) => None,
+            Component::Panel(ref panel) => Some(&panel.children),
+            Component::Container(ref container) => Some(container.children()),
+        }
+    }
+    fn children_mut(&mut self) -> Option<&mut Vec<ComponentIndex>> {
+        match *self {
+            Component::Control(_) | Component::Chart(_) => None,
+            Component::Panel(ref mut panel) => Some(&mut panel.children),
+            Component::Container(ref mut container) => Some(container.children_mut()),
+        }
+    }
+}
+impl<St> CommonProperties for Component<St> {
+    fn common(&self) -> &CommonProps {
+        match *self {
+            Component::Control(ref ctrl) => ctrl.common(),
+            Component::Panel(ref panel) => panel.common(),
+            Component::Container(ref container) => container.common(),
+            Component::Chart(ref chart) => chart.common(),
+        }
+    }
+    fn common_mut(&mut self) -> &mut CommonProps {
+        match *self {
+            Component::Control(ref mut ctrl) => ctrl.common_mut(),
+            Component::Panel(ref mut panel) => panel.common_mut(),
+            Component::Container(ref mut container) => container.common_mut(),
+            Component::Chart(ref mut chart) => chart.common_mut(),
+        }
+    }
 }
 impl<St> HandleEvent<St> for Component<St> {
     fn handle_event(&self, event: Event, state: St) -> error::Result<St> {
@@ -25,6 +65,12 @@ impl<St> HandleEvent<St> for Component<St> {
             },
             Component::Panel(ref panel) => {
                 panel.handle_event(event, state)
+            },
+            Component::Container(ref container) => {
+                container.handle_event(event, state)
+            },
+            Component::Chart(ref chart) => {
+                chart.handle_event(event, state)
             }
         }
     }
@@ -32,15 +78,67 @@ impl<St> HandleEvent<St> for Component<St> {
 
 #[derive(Debug, Serialize, Clone)]
 pub struct Panel {
+    #[serde(flatten)]
+    common: CommonProps,
+    kind: PanelKind,
+    /// Spacing between children, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gap: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    align: Option<Align>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    justify: Option<Align>,
+    /// Whether children of row panels wrap onto multiple lines.
+    wrap: bool,
     children: Vec<ComponentIndex>
 }
+impl Default for Panel {
+    fn default() -> Panel { Panel::with_kind(PanelKind::default()) }
+}
 impl Panel {
     pub fn new() -> Panel {
-        Panel { children: vec![] }
+        Panel::default()
+    }
+    pub fn with_kind(kind: PanelKind) -> Panel {
+        Panel {
+            common: CommonProps::default(),
+            kind,
+            gap: None,
+            align: None,
+            justify: None,
+            wrap: false,
+            children: vec![]
+        }
+    }
+    pub fn row() -> Panel { Panel::with_kind(PanelKind::Row) }
+    pub fn column() -> Panel { Panel::with_kind(PanelKind::Column) }
+    pub fn grid(columns: usize) -> Panel { Panel::with_kind(PanelKind::Grid { columns }) }
+
+    pub fn with_gap(self, gap: u32) -> Panel {
+        Panel { gap: Some(gap), ..self }
+    }
+    pub fn with_align(self, align: Align) -> Panel {
+        Panel { align: Some(align), ..self }
+    }
+    pub fn with_justify(self, justify: Align) -> Panel {
+        Panel { justify: Some(justify), ..self }
+    }
+    pub fn with_wrap(self, wrap: bool) -> Panel {
+        Panel { wrap, ..self }
+    }
+    pub fn kind(&self) -> PanelKind {
+        self.kind
     }
     pub fn add_child(&mut self, component_idx: ComponentIndex) {
         self.children.push(component_idx);
     }
+    pub fn children(&self) -> &[ComponentIndex] {
+        &self.children
+    }
+}
+impl CommonProperties for Panel {
+    fn common(&self) -> &CommonProps { &self.common }
+    fn common_mut(&mut self) -> &mut CommonProps { &mut self.common }
 }
 impl<St> HandleEvent<St> for Panel {
     fn handle_event(&self, event: Event, _: St) -> error::Result<St> {
@@ -55,7 +153,67 @@ impl<St> From<Control<St>> for Component<St> {
     fn from(control: Control<St>) -> Component<St> { Component::Control(control) }
 }
 
-#[derive(Debug)]
+/// Structural change to a layout, requested by an `Update`.
+#[derive(Debug, Clone)]
+pub enum LayoutChange<St> {
+    /// Add a new panel to `parent` (or the top level), optionally at a specific position.
+    AddPanel {
+        name: String,
+        panel: Panel,
+        parent: Option<ComponentIndex>,
+        position: Option<usize>,
+    },
+    /// Add a new control to a panel, optionally at a specific position.
+    AddControl {
+        name: String,
+        control: Control<St>,
+        panel: ComponentIndex,
+        position: Option<usize>,
+    },
+    /// Remove a component (and, for panels, all of its descendants).
+    Remove(ComponentIndex),
+    /// Move a component to a position in `parent` (or the top level).
+    Move {
+        idx: ComponentIndex,
+        parent: Option<ComponentIndex>,
+        position: usize,
+    },
+}
+impl<St> LayoutChange<St> {
+    /// Whether this change has already been made to `layout`: a component with the same name has
+    /// been added, or the component has been removed.
+    pub fn is_applied(&self, layout: &Layout<St>) -> bool {
+        match *self {
+            LayoutChange::AddPanel { ref name, .. } | LayoutChange::AddControl { ref name, .. } =>
+                layout.get_component_index(name).is_some(),
+            LayoutChange::Remove(idx) => layout.is_removed(idx),
+            LayoutChange::Move { .. } => false,
+        }
+    }
+}
+
+/// Applied layout change, as communicated to the client.
+#[derive(Debug, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase", bound(serialize = "St: ChartState"))]
+pub enum LayoutDiff<St> {
+    Add {
+        component: IndexedComponent<St>,
+        parent: Option<ComponentIndex>,
+        position: usize,
+    },
+    Remove {
+        removed: Vec<ComponentIndex>,
+    },
+    Move {
+        idx: ComponentIndex,
+        parent: Option<ComponentIndex>,
+        position: usize,
+    },
+}
+
+/// Tree of panels, containers, controls, and charts making up a dashboard. The layout an app is
+/// created with is never modified; each client session works on its own copy (see `session`).
+#[derive(Debug, Clone)]
 pub struct Layout<St> {
     component_store: ComponentStore<St>,
     registry: ComponentRegistry,
@@ -76,9 +234,39 @@ impl<St> Layout<St> {
 
     pub fn add_panel<S: AsRef<str>>(&mut self, name: S,
         parent: Option<ComponentIndex>) -> error::Result<ComponentIndex>
+    {
+        self.add_panel_with(name, Panel::new(), parent)
+    }
+
+    /// Add a panel with the specified kind and properties (e.g. `Panel::row().with_gap(8)`).
+    pub fn add_panel_with<S: AsRef<str>>(&mut self, name: S, panel: Panel,
+        parent: Option<ComponentIndex>) -> error::Result<ComponentIndex>
+    {
+        self.add_child_component(name, panel, parent)
+    }
+
+    /// Add a container (tabs, accordion, or modal) to `parent` (or the top level).
+    pub fn add_container<S: AsRef<str>, C: Into<Container<St>>>(&mut self, name: S, container: C,
+        parent: Option<ComponentIndex>) -> error::Result<ComponentIndex>
+    {
+        self.add_child_component(name, container.into(), parent)
+    }
+
+    /// Add a chart to `parent` (or the top level). Its figure is provided by updates.
+    pub fn add_chart<S: AsRef<str>>(&mut self, name: S, chart: ChartComponent<St>,
+        parent: Option<ComponentIndex>) -> error::Result<ComponentIndex>
+    {
+        self.add_child_component(name, chart, parent)
+    }
+
+    fn add_child_component<S: AsRef<str>, C: Into<Component<St>>>(&mut self, name: S,
+        component: C, parent: Option<ComponentIndex>) -> error::Result<ComponentIndex>
     {
         let name = name.as_ref().to_string();
-        let new_idx = self.add_component(name.clone(), Panel::new());
+        if let Some(parent_idx) = parent {
+            self.check_panel(&name, parent_idx)?;
+        }
+        let new_idx = self.add_component(name.clone(), component)?;
         match parent {
             Some(parent_idx) => {
                 self.add_to_panel(name, new_idx, parent_idx)?;
@@ -94,7 +282,8 @@ impl<St> Layout<St> {
         &mut self, name: S, control: C, panel_idx: ComponentIndex) -> error::Result<ComponentIndex>
     {
         let name = name.as_ref().to_string();
-        let control_idx = self.add_component(name.clone(), control.into());
+        self.check_panel(&name, panel_idx)?;
+        let control_idx = self.add_component(name.clone(), control.into())?;
         self.add_to_panel(name, control_idx, panel_idx)?;
         Ok(control_idx)
     }
@@ -103,14 +292,195 @@ impl<St> Layout<St> {
         self.registry.get(name.as_ref()).cloned()
     }
 
+    /// Returns the component at the specified index, or `None` if no such component exists.
+    pub fn get(&self, idx: ComponentIndex) -> Option<&Component<St>> {
+        self.component_store.0.get(idx).and_then(|c| c.as_ref())
+    }
+
+    /// Returns a mutable reference to the component at the specified index, or `None` if no such
+    /// component exists.
+    pub fn get_mut(&mut self, idx: ComponentIndex) -> Option<&mut Component<St>> {
+        self.component_store.0.get_mut(idx).and_then(|c| c.as_mut())
+    }
+
+    /// Whether the component at `idx` existed but has since been removed.
+    pub fn is_removed(&self, idx: ComponentIndex) -> bool {
+        match self.component_store.0.get(idx) {
+            Some(&None) => true,
+            _ => false
+        }
+    }
+
+    /// Parent panel (or container) of the specified component, or `None` for top-level (or
+    /// unknown) components.
+    pub fn parent(&self, idx: ComponentIndex) -> Option<ComponentIndex> {
+        self.component_store.0.iter().position(|component| {
+            match component.as_ref().and_then(|c| c.children()) {
+                Some(children) => children.contains(&idx),
+                None => false
+            }
+        })
+    }
+
+    /// Whether the specified component, and all of the panels containing it, are enabled.
+    pub fn is_enabled(&self, idx: ComponentIndex) -> bool {
+        let mut next = Some(idx);
+        while let Some(idx) = next {
+            match self.get(idx) {
+                Some(component) if component.common().enabled => {},
+                _ => return false,
+            }
+            next = self.parent(idx);
+        }
+        true
+    }
+
+    /// Top-level components (if `parent` is `None`) or children of the specified panel.
+    pub fn children(&self, parent: Option<ComponentIndex>) -> Option<&[ComponentIndex]> {
+        match parent {
+            None => Some(&self.children),
+            Some(idx) => self.get(idx).and_then(|c| c.children()),
+        }
+    }
+
+    /// Remove a component from the layout. Removing a panel also removes all of its descendants.
+    /// Returns the indices of all removed components. Indices of other components are unaffected.
+    pub fn remove_component(&mut self, idx: ComponentIndex)
+        -> error::Result<Vec<ComponentIndex>>
+    {
+        if self.get(idx).is_none() {
+            return Err(error::RhubarbError::UnknownComponent(idx));
+        }
+        self.detach(idx);
+        let mut removed = vec![];
+        let mut pending = vec![idx];
+        while let Some(next) = pending.pop() {
+            if let Some(mut component) = self.component_store.0[next].take() {
+                if let Some(children) = component.children_mut() {
+                    pending.extend(children.drain(..));
+                }
+            }
+            removed.push(next);
+        }
+        self.registry.retain(|_, idx| !removed.contains(idx));
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Move a component to `position` within `parent` (or the top level if `parent` is `None`).
+    pub fn move_component(&mut self, idx: ComponentIndex, parent: Option<ComponentIndex>,
+        position: usize) -> error::Result<usize>
+    {
+        if self.get(idx).is_none() {
+            return Err(error::RhubarbError::UnknownComponent(idx));
+        }
+        if let Some(parent_idx) = parent {
+            self.check_panel(idx.to_string(), parent_idx)?;
+            if parent_idx == idx || self.is_descendant(parent_idx, idx) {
+                return Err(error::RhubarbError::InvalidLayout(
+                    format!("unable to move component {} into itself", idx)));
+            }
+        }
+        self.detach(idx);
+        let children = match parent {
+            None => &mut self.children,
+            Some(parent_idx) => self.component_store.0[parent_idx].as_mut()
+                .and_then(|c| c.children_mut())
+                .expect("parent checked to be a panel"),
+        };
+        let position = position.min(children.len());
+        children.insert(position, idx);
+        Ok(position)
+    }
+
+    /// Apply a structural change to this layout.
+    pub fn apply_change(&mut self, change: LayoutChange<St>) -> error::Result<LayoutDiff<St>>
+        where St: Clone
+    {
+        match change {
+            LayoutChange::AddPanel { name, panel, parent, position } => {
+                let idx = self.add_panel_with(name, panel, parent)?;
+                self.added(idx, parent, position)
+            },
+            LayoutChange::AddControl { name, control, panel, position } => {
+                let idx = self.add_control_to_panel(name, control, panel)?;
+                self.added(idx, Some(panel), position)
+            },
+            LayoutChange::Remove(idx) => {
+                Ok(LayoutDiff::Remove { removed: self.remove_component(idx)? })
+            },
+            LayoutChange::Move { idx, parent, position } => {
+                let position = self.move_component(idx, parent, position)?;
+                Ok(LayoutDiff::Move { idx, parent, position })
+            }
+        }
+    }
+    fn added(&mut self, idx: ComponentIndex, parent: Option<ComponentIndex>,
+        position: Option<usize>) -> error::Result<LayoutDiff<St>>
+        where St: Clone
+    {
+        let position = match position {
+            Some(position) => self.move_component(idx, parent, position)?,
+            None => self.children(parent).map(|children| children.len() - 1).unwrap_or(0),
+        };
+        let component = self.get(idx).cloned()
+            .ok_or(error::RhubarbError::UnknownComponent(idx))?;
+        Ok(LayoutDiff::Add {
+            component: IndexedComponent { idx, component },
+            parent,
+            position,
+        })
+    }
+
+    /// Remove a component from the children list of its parent.
+    fn detach(&mut self, idx: ComponentIndex) {
+        self.children.retain(|&child| child != idx);
+        for component in self.component_store.0.iter_mut() {
+            if let Some(children) = component.as_mut().and_then(|c| c.children_mut()) {
+                children.retain(|&child| child != idx);
+            }
+        }
+    }
+    /// Whether `idx` is contained (at any depth) within the panel `ancestor`.
+    fn is_descendant(&self, idx: ComponentIndex, ancestor: ComponentIndex) -> bool {
+        match self.get(ancestor).and_then(|c| c.children()) {
+            Some(children) => children.iter()
+                .any(|&child| child == idx || self.is_descendant(idx, child)),
+            None => false
+        }
+    }
+
+    fn check_name<S: AsRef<str>>(&self, name: S) -> error::Result<()> {
+        if self.registry.contains_key(name.as_ref()) {
+            Err(error::RhubarbError::DuplicateComponent(name.as_ref().into()))
+        } else {
+            Ok(())
+        }
+    }
+    fn check_panel<S: AsRef<str>>(&self, name: S, panel_idx: ComponentIndex)
+        -> error::Result<()>
+    {
+        match self.get(panel_idx) {
+            Some(component) if component.children().is_some() => Ok(()),
+            None => Err(error::RhubarbError::UnknownComponent(panel_idx)),
+            Some(_) => Err(error::RhubarbError::InvalidLayout(
+                format!("unable able to add component {} to non-panel with index {}",
+                    name.as_ref(), panel_idx)))
+        }
+    }
     fn add_to_panel<S: AsRef<str>>(&mut self, name: S, component_idx: ComponentIndex,
         panel_idx: ComponentIndex) -> error::Result<()>
     {
-        match self.component_store.0[panel_idx] {
-            Component::Panel(ref mut panel) => {
-                panel.add_child(component_idx);
+        match self.component_store.0.get_mut(panel_idx).and_then(|c| c.as_mut())
+            .map(|c| c.children_mut())
+        {
+            Some(Some(children)) => {
+                children.push(component_idx);
             },
-            _ => {
+            None => {
+                return Err(error::RhubarbError::UnknownComponent(panel_idx));
+            },
+            Some(None) => {
                 return Err(error::RhubarbError::InvalidLayout(
                     format!("unable able to add component {} to non-panel with index {}",
                         name.as_ref(), panel_idx)));
@@ -119,18 +489,68 @@ impl<St> Layout<St> {
         Ok(())
     }
     fn add_component<S: AsRef<str>, C: Into<Component<St>>>(&mut self, name: S, component: C)
-        -> ComponentIndex
+        -> error::Result<ComponentIndex>
     {
+        self.check_name(&name)?;
         let new_idx = self.component_store.0.len();
-        self.component_store.0.push(component.into());
-        self.registry.entry(name.as_ref().into()).or_insert(new_idx);
-        new_idx
+        self.component_store.0.push(Some(component.into()));
+        self.registry.insert(name.as_ref().into(), new_idx);
+        Ok(new_idx)
+    }
+
+    /// Record changes a client makes to a component by itself (switching tabs, or closing a
+    /// modal), so the component isn't reverted when it's next sent to the client. The client has
+    /// already made the change when it sends the event, so this should be called before the
+    /// event is handled, whether or not handling succeeds.
+    ///
+    /// Timer ticks are also counted here: the tick count sent by the client is replaced by the
+    /// count kept in the layout, and the timer is stopped once it reaches its `max_ticks`.
+    pub fn sync_event(&mut self, event: &mut EventMessage) {
+        if !self.is_enabled(event.idx) {
+            return;
+        }
+        let num_children = self.children(Some(event.idx)).map_or(0, |children| children.len());
+        let component = match self.get_mut(event.idx) {
+            Some(component) => component,
+            None => return,
+        };
+        match (component, &mut event.event) {
+            (&mut Component::Container(Container::Tabs(ref mut tabs)),
+                &mut Event::TabChange(ref change)) =>
+            {
+                if change.idx < num_children {
+                    tabs.active = change.idx;
+                }
+            },
+            (&mut Component::Container(Container::Modal(ref mut modal)),
+                &mut Event::ModalClose(_)) =>
+            {
+                modal.open = false;
+            },
+            (&mut Component::Control(Control::Timer(ref mut timer)),
+                &mut Event::Tick(ref mut tick)) =>
+            {
+                tick.count = timer.record_tick();
+            },
+            _ => {}
+        }
     }
 
     pub fn handle_event(&self, event: EventMessage, state: St) -> error::Result<St> {
-        self.component_store.0[event.idx].handle_event(event.event, state)
+        match self.get(event.idx) {
+            Some(_) if !self.is_enabled(event.idx) =>
+                Err(error::RhubarbError::DisabledComponent(event.idx)),
+            Some(component) => component.handle_event(event.event, state),
+            None => Err(error::RhubarbError::UnknownComponent(event.idx))
+        }
     }
 }
+/// Access a component by index.
+///
+/// # Panics
+///
+/// Panics if there's no component at `index`, or if it has been removed from the layout. Use
+/// `Layout::get` for indices received from clients, which may refer to removed components.
 impl<St> Index<ComponentIndex> for Layout<St> {
     type Output = Component<St>;
     fn index(&self, index: ComponentIndex) -> &Component<St> {
@@ -146,13 +566,17 @@ impl<St: ChartState> Serialize for Layout<St> {
     }
 }
 
+/// Component storage. Removed components leave an empty slot, so indices remain stable.
 #[derive(Debug, Clone)]
-struct ComponentStore<St>(Vec<Component<St>>);
+struct ComponentStore<St>(Vec<Option<Component<St>>>);
 impl<St: ChartState> Serialize for ComponentStore<St> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        let mut seq = serializer.serialize_seq(Some(self.0.iter().filter(|c| c.is_some())
+            .count()))?;
         for (idx, component) in self.0.iter().enumerate() {
-            seq.serialize_element(&IndexedComponentRef { idx, component })?;
+            if let Some(ref component) = *component {
+                seq.serialize_element(&IndexedComponentRef { idx, component })?;
+            }
         }
         seq.end()
     }
@@ -160,7 +584,7 @@ impl<St: ChartState> Serialize for ComponentStore<St> {
 impl<St> Index<ComponentIndex> for ComponentStore<St> {
     type Output = Component<St>;
     fn index(&self, index: ComponentIndex) -> &Component<St> {
-        &self.0[index]
+        self.0[index].as_ref().expect("component has been removed from layout")
     }
 }
 
@@ -170,6 +594,7 @@ struct IndexedComponentRef<'a, St: 'a> {
     component: &'a Component<St>
 }
 
+#[derive(Debug)]
 pub struct IndexedComponent<St> {
     pub idx: ComponentIndex,
     pub component: Component<St>
@@ -188,6 +613,12 @@ impl<$($lifetime,)* St: ChartState> Serialize for $name<$($lifetime,)* St> {
             },
             Component::Panel(ref panel) => {
                 state.serialize_field("panel", panel)?;
+            },
+            Component::Container(ref container) => {
+                state.serialize_field("container", container)?;
+            },
+            Component::Chart(ref chart) => {
+                state.serialize_field("chart", chart)?;
             }
         }
         state.end()
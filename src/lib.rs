@@ -8,33 +8,77 @@ extern crate serde;
 #[macro_use] extern crate serde_derive;
 extern crate serde_json;
 extern crate rhubarb_graph;
-extern crate unicase;
+extern crate base64;
+extern crate rand;
+extern crate linked_hash_map;
+#[macro_use] extern crate log;
+#[cfg(feature = "toml")]
+extern crate toml;
+#[cfg(feature = "yaml")]
+extern crate serde_yaml;
+#[cfg(feature = "yaml")]
+extern crate yaml_rust;
+#[cfg(feature = "sqlite")]
+extern crate rusqlite;
 
 pub mod control;
+pub mod container;
+pub mod chart;
+pub mod linked;
 pub mod handler;
 pub mod router;
 pub mod resource;
 pub mod error;
 pub mod logger;
 pub mod layout;
+pub mod props;
 pub mod event;
 pub mod update;
+pub mod assets;
+pub mod export;
+pub mod definition;
+pub mod page;
+pub mod share;
+pub mod store;
+pub mod history;
+pub mod session;
 
 use std::sync::Arc;
 use std::panic::RefUnwindSafe;
+use std::path::Path;
 
 use serde::{Serialize, Serializer};
 use serde::de::DeserializeOwned;
+use gotham::router::Router;
+use gotham::router::builder::DrawRoutes;
+use gotham::pipeline::chain::PipelineHandleChain;
 
 use rhubarb_graph as rg;
 
 use layout::Layout;
 use update::GenerateUpdate;
+use page::{Page, Dashboard};
+use resource::{PageConfig, PlotlySource, Script, Style, Meta, TemplateSource};
+use assets::AssetSource;
 
 #[derive(Debug)]
 pub enum Chart {
     Scatter(rg::Graph<rg::scatter::Scatter>)
 }
+impl Chart {
+    /// Render this chart as an SVG document.
+    pub fn to_svg(&self) -> error::Result<String> {
+        match *self {
+            Chart::Scatter(ref graph) => Ok(graph.to_svg()?)
+        }
+    }
+    /// Convert this chart into an equivalent Vega-Lite specification.
+    pub fn to_vega_lite(&self) -> error::Result<serde_json::Value> {
+        match *self {
+            Chart::Scatter(ref graph) => Ok(graph.to_vega_lite()?)
+        }
+    }
+}
 impl Serialize for Chart {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         match *self {
@@ -50,17 +94,148 @@ impl<T> ChartState for T where T:
 
 
 pub struct RhubarbApp<St> {
-    layout: Arc<Layout<St>>
+    layout: Arc<Layout<St>>,
+    page: PageConfig,
+    assets: AssetSource,
+    pages: Vec<Arc<Page>>,
+    history_depth: Option<usize>,
 }
 impl<St: ChartState> RhubarbApp<St> {
     pub fn new(layout: Layout<St>) -> RhubarbApp<St> {
         RhubarbApp {
-            layout: Arc::new(layout)
+            layout: Arc::new(layout),
+            page: PageConfig::default(),
+            assets: AssetSource::default(),
+            pages: vec![],
+            history_depth: None,
         }
     }
-    pub fn start<Gen: GenerateUpdate<St> + 'static>(&self, gen: Gen) {
+    /// Set the title of the index page.
+    pub fn title<S: AsRef<str>>(mut self, title: S) -> RhubarbApp<St> {
+        self.page.title = title.as_ref().into();
+        self
+    }
+    /// Add a script to the index page, loaded after the default scripts.
+    pub fn script<S: AsRef<str>>(mut self, url: S) -> RhubarbApp<St> {
+        self.page.scripts.push(Script { url: url.as_ref().into() });
+        self
+    }
+    /// Add a stylesheet to the index page, loaded after the default stylesheets.
+    pub fn style<S: AsRef<str>>(mut self, url: S) -> RhubarbApp<St> {
+        self.page.styles.push(Style { url: url.as_ref().into() });
+        self
+    }
+    /// Add a `<meta>` tag to the index page.
+    pub fn meta<N: AsRef<str>, C: AsRef<str>>(mut self, name: N, content: C) -> RhubarbApp<St> {
+        self.page.meta.push(Meta { name: name.as_ref().into(), content: content.as_ref().into() });
+        self
+    }
+    /// Set the favicon URL of the index page.
+    pub fn favicon<S: AsRef<str>>(mut self, url: S) -> RhubarbApp<St> {
+        self.page.favicon = Some(url.as_ref().into());
+        self
+    }
+    /// Use the Handlebars template at `path` to render the index page instead of the default.
+    pub fn template_file<P: AsRef<Path>>(mut self, path: P) -> RhubarbApp<St> {
+        self.page.template = TemplateSource::File(path.as_ref().into());
+        self
+    }
+    /// Use the provided Handlebars template to render the index page instead of the default.
+    pub fn template<S: AsRef<str>>(mut self, template: S) -> RhubarbApp<St> {
+        self.page.template = TemplateSource::Inline(template.as_ref().into());
+        self
+    }
+    /// Register a Handlebars partial (from the file at `path`) for use by the index template.
+    pub fn partial_file<S: AsRef<str>, P: AsRef<Path>>(mut self, name: S, path: P)
+        -> RhubarbApp<St>
+    {
+        self.page.partials.push((name.as_ref().into(), TemplateSource::File(path.as_ref().into())));
+        self
+    }
+    /// Register a Handlebars partial for use by the index template.
+    pub fn partial<S: AsRef<str>, T: AsRef<str>>(mut self, name: S, partial: T)
+        -> RhubarbApp<St>
+    {
+        self.page.partials.push((name.as_ref().into(),
+            TemplateSource::Inline(partial.as_ref().into())));
+        self
+    }
+    /// Load the specified version of plotly.js from the Plotly CDN.
+    pub fn plotly_version<S: AsRef<str>>(mut self, version: S) -> RhubarbApp<St> {
+        self.page.plotly = PlotlySource::Cdn { version: version.as_ref().into() };
+        self
+    }
+    /// Serve plotly.js from the vendored file at `path` instead of the Plotly CDN, for use on
+    /// machines without internet access. Starting the app fails if this file cannot be read.
+    pub fn offline_plotly<P: AsRef<Path>>(mut self, path: P) -> RhubarbApp<St> {
+        self.page.plotly = PlotlySource::Local(path.as_ref().into());
+        self
+    }
+    /// Set the location front-end assets (bundle, stylesheet, and default index template) are
+    /// served from. Defaults to `AssetSource::default()`, which can be set at runtime with the
+    /// `RHUBARB_ASSETS` environment variable.
+    pub fn assets(mut self, assets: AssetSource) -> RhubarbApp<St> {
+        self.assets = assets;
+        self
+    }
+    /// Host an additional dashboard, with its own layout, state type, and update generator, at
+    /// `/dash/{name}`. When an app has additional pages, every dashboard shows navigation links
+    /// to all the others. Starting the app fails if page names are not unique or are not valid
+    /// URL path segments.
+    pub fn page<N, T, PSt, Gen>(mut self, name: N, title: T, layout: Layout<PSt>, gen: Gen)
+        -> RhubarbApp<St>
+        where N: AsRef<str>, T: AsRef<str>, PSt: ChartState, Gen: GenerateUpdate<PSt> + 'static
+    {
+        self.pages.push(Arc::new(Dashboard::new(name, title, layout, gen)));
+        self
+    }
+    /// Keep an undo/redo history of up to `depth` states for each client session, used by
+    /// `HistoryControl`s. Applies to the main dashboard and all additional pages. See `history`.
+    pub fn history(mut self, depth: usize) -> RhubarbApp<St> {
+        self.history_depth = Some(depth);
+        self
+    }
+    /// Render the charts of the dashboard, as they would initially appear with the provided
+    /// state, into a single self-contained HTML document. Requires a local copy of plotly.js (see
+    /// `offline_plotly`). See `export::snapshot_html`.
+    pub fn export_snapshot<Gen: GenerateUpdate<St>>(&self, gen: &Gen, state: St)
+        -> error::Result<String>
+    {
+        export::snapshot_html(&self.layout, gen, state, &self.page)
+    }
+    /// Routes serving this app under `prefix`. URLs on the rendered pages are adjusted to be
+    /// served under `prefix`, so the router must be mounted at exactly that path; `mount` does
+    /// both from a single prefix.
+    pub fn router<Gen: GenerateUpdate<St> + 'static>(&self, gen: Gen, prefix: &str)
+        -> error::Result<Router>
+    {
+        let mut page = self.page.clone();
+        page.set_base_url(prefix);
+        router::router(gen, self.layout.clone(), &page, &self.assets, &self.pages,
+            self.history_depth)
+    }
+    /// Mount this app inside an existing gotham application under `prefix`, e.g.:
+    ///
+    /// ```ignore
+    /// build_simple_router(|route| {
+    ///     app.mount(route, "/reports", gen).unwrap();
+    /// })
+    /// ```
+    pub fn mount<D, C, P, Gen>(&self, route: &mut D, prefix: &str, gen: Gen) -> error::Result<()>
+        where D: DrawRoutes<C, P>,
+              C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
+              P: RefUnwindSafe + Send + Sync + 'static,
+              Gen: GenerateUpdate<St> + 'static
+    {
+        let router = self.router(gen, prefix)?;
+        route.delegate(&format!("/{}", prefix.trim_matches('/'))).to_router(router);
+        Ok(())
+    }
+    pub fn start<Gen: GenerateUpdate<St> + 'static>(&self, gen: Gen) -> error::Result<()> {
         let addr = "127.0.0.1:7878";
-        println!("Listening for requests at http://{}", addr);
-        gotham::start(addr, router::router(gen, self.layout.clone()))
+        let router = self.router(gen, "/")?;
+        info!("Listening for requests at http://{}", addr);
+        gotham::start(addr, router);
+        Ok(())
     }
 }
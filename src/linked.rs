@@ -0,0 +1,143 @@
+//! Helpers for linking selections between charts (brushing and linking).
+//!
+//! A selection made in one chart is stored in the dashboard state as a `LinkedSelection`. When
+//! generating the next update, the selection is mapped back to `DataView` rows using the `RowMap`
+//! of the chart it was made in, and the resulting `RowSet` is applied to the traces of the other
+//! charts backed by the same data, either highlighting or filtering the selected rows.
+
+use std::collections::BTreeSet;
+
+use agnes::DataView;
+use rhubarb_graph::scatter::Scatter;
+
+use chart::{ChartSelect, PointRef};
+use layout::ComponentIndex;
+
+/// Points selected in one chart of a dashboard. Intended to be stored in the dashboard state and
+/// updated from a chart component's `on_select` handler.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkedSelection {
+    source: Option<ComponentIndex>,
+    points: Vec<PointRef>,
+}
+impl LinkedSelection {
+    pub fn new() -> LinkedSelection {
+        LinkedSelection::default()
+    }
+    /// Replace the current selection with the selected points of chart `source`. Selecting no
+    /// points clears the selection.
+    pub fn select(&mut self, source: ComponentIndex, select: ChartSelect) {
+        if select.points.is_empty() {
+            self.clear();
+        } else {
+            self.source = Some(source);
+            self.points = select.points;
+        }
+    }
+    pub fn clear(&mut self) {
+        self.source = None;
+        self.points.clear();
+    }
+    /// Chart in which the current selection was made, if any.
+    pub fn source(&self) -> Option<ComponentIndex> {
+        self.source
+    }
+    pub fn points(&self) -> &[PointRef] {
+        &self.points
+    }
+    pub fn is_active(&self) -> bool {
+        self.source.is_some()
+    }
+    /// Rows of the underlying data which are selected, given the row map of the source chart.
+    /// Returns `None` if there is no active selection. Points which do not appear in the row map
+    /// are ignored.
+    pub fn rows(&self, row_map: &RowMap) -> Option<RowSet> {
+        if !self.is_active() {
+            return None;
+        }
+        Some(self.points.iter().filter_map(|&point| row_map.row(point)).collect())
+    }
+}
+
+/// Mapping from the points of a chart's traces to the `DataView` rows they were generated from.
+/// Traces are registered in the same order they are added to the chart.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowMap {
+    traces: Vec<Vec<usize>>,
+}
+impl RowMap {
+    pub fn new() -> RowMap {
+        RowMap::default()
+    }
+    /// Row map of a chart with a single trace containing every row of a `DataView` with `nrows`
+    /// rows, in order.
+    pub fn identity(nrows: usize) -> RowMap {
+        RowMap { traces: vec![(0..nrows).collect()] }
+    }
+    /// Row map of a chart with a single trace containing every row of `view`, in order.
+    pub fn from_view(view: &DataView) -> RowMap {
+        RowMap::identity(view.nrows())
+    }
+    /// Row map of a chart with a single trace containing the rows (of a `DataView` with `nrows`
+    /// rows) for which `filter` returns `true`, in order.
+    pub fn filtered<F: Fn(usize) -> bool>(nrows: usize, filter: F) -> RowMap {
+        RowMap { traces: vec![(0..nrows).filter(|&row| filter(row)).collect()] }
+    }
+    /// Register a trace whose `i`th point was generated from row `rows[i]`. Returns the index of
+    /// the trace.
+    pub fn add_trace(&mut self, rows: Vec<usize>) -> usize {
+        self.traces.push(rows);
+        self.traces.len() - 1
+    }
+    /// Row the referenced point was generated from.
+    pub fn row(&self, point: PointRef) -> Option<usize> {
+        self.traces.get(point.trace).and_then(|rows| rows.get(point.point)).cloned()
+    }
+    /// Rows of the points of trace `trace`.
+    pub fn trace_rows(&self, trace: usize) -> Option<&[usize]> {
+        self.traces.get(trace).map(|rows| rows.as_slice())
+    }
+    pub fn num_traces(&self) -> usize {
+        self.traces.len()
+    }
+}
+
+/// Set of selected `DataView` rows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowSet(BTreeSet<usize>);
+impl RowSet {
+    pub fn contains(&self, row: usize) -> bool {
+        self.0.contains(&row)
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn iter<'a>(&'a self) -> Box<Iterator<Item=usize> + 'a> {
+        Box::new(self.0.iter().cloned())
+    }
+    /// Indices of the points of a trace which were generated from selected rows, where
+    /// `trace_rows` are the rows of the trace's points (see `RowMap::trace_rows`).
+    pub fn selected_points(&self, trace_rows: &[usize]) -> Vec<usize> {
+        trace_rows.iter().enumerate()
+            .filter(|&(_, row)| self.contains(*row))
+            .map(|(point, _)| point)
+            .collect()
+    }
+    /// Keep only the selected rows, preserving their order. Useful for filtering the rows of a
+    /// sibling chart before generating its traces.
+    pub fn filter(&self, rows: &[usize]) -> Vec<usize> {
+        rows.iter().cloned().filter(|&row| self.contains(row)).collect()
+    }
+    /// Highlight the selected rows in a trace, de-emphasizing the others.
+    pub fn highlight(&self, scatter: Scatter, trace_rows: &[usize]) -> Scatter {
+        scatter.selected_points(self.selected_points(trace_rows))
+    }
+}
+impl ::std::iter::FromIterator<usize> for RowSet {
+    fn from_iter<I: IntoIterator<Item=usize>>(iter: I) -> RowSet {
+        RowSet(iter.into_iter().collect())
+    }
+}
@@ -1,5 +1,110 @@
 use std::fmt::Display;
+use std::io;
+use std::time::{Duration, Instant};
+
+use futures::Future;
+use gotham::handler::HandlerFuture;
+use gotham::middleware::{Middleware, NewMiddleware};
+use gotham::state::{State, FromState};
+use hyper::{Method, Uri, StatusCode};
+
+use layout::ComponentIndex;
 
 pub fn log_handler_err<S: AsRef<str>, E: Display>(handler_name: S, err: E) {
-    println!("Error during handler '{}': {}", handler_name.as_ref(), err);
+    error!("Error during handler '{}': {}", handler_name.as_ref(), err);
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 + duration.subsec_nanos() as f64 / 1_000_000.0
+}
+
+/// Collects details about a single request as it is handled, and logs them as a single line
+/// once the request completes.
+#[derive(Debug, Clone)]
+pub struct RequestSpan {
+    method: String,
+    path: String,
+    start: Instant,
+    component: Option<ComponentIndex>,
+    event: Option<String>,
+    update_latency: Option<Duration>,
+}
+impl RequestSpan {
+    pub fn new(state: &State) -> RequestSpan {
+        RequestSpan {
+            method: Method::borrow_from(state).to_string(),
+            path: Uri::borrow_from(state).path().to_string(),
+            start: Instant::now(),
+            component: None,
+            event: None,
+            update_latency: None,
+        }
+    }
+    pub fn set_component(&mut self, idx: ComponentIndex) {
+        self.component = Some(idx);
+    }
+    pub fn set_event<S: AsRef<str>>(&mut self, name: S) {
+        self.event = Some(name.as_ref().to_string());
+    }
+    pub fn set_update_latency(&mut self, latency: Duration) {
+        self.update_latency = Some(latency);
+    }
+    /// Log the completed request with its response status.
+    pub fn finish(self, status: StatusCode) {
+        let mut details = String::new();
+        if let Some(idx) = self.component {
+            details.push_str(&format!(" component={}", idx));
+        }
+        if let Some(ref event) = self.event {
+            details.push_str(&format!(" event={}", event));
+        }
+        if let Some(latency) = self.update_latency {
+            details.push_str(&format!(" update_ms={:.3}", millis(latency)));
+        }
+        info!(target: "rhubarb::request", "{} {} status={}{} total_ms={:.3}",
+            self.method, self.path, status.as_u16(), details, millis(self.start.elapsed()));
+    }
+    /// Log a request which failed without producing a response.
+    pub fn fail(self) {
+        warn!(target: "rhubarb::request", "{} {} failed total_ms={:.3}",
+            self.method, self.path, millis(self.start.elapsed()));
+    }
+}
+
+/// Middleware which writes an access log entry for every request.
+#[derive(Debug, Clone, Copy)]
+pub struct AccessLogger;
+
+impl NewMiddleware for AccessLogger {
+    type Instance = AccessLogger;
+
+    fn new_middleware(&self) -> io::Result<AccessLogger> {
+        Ok(*self)
+    }
+}
+
+impl Middleware for AccessLogger {
+    fn call<Chain>(self, state: State, chain: Chain) -> Box<HandlerFuture>
+        where Chain: FnOnce(State) -> Box<HandlerFuture> + 'static, Self: Sized
+    {
+        let method = Method::borrow_from(&state).to_string();
+        let path = Uri::borrow_from(&state).path().to_string();
+        let start = Instant::now();
+        let future = chain(state).then(move |result| {
+            let elapsed = millis(start.elapsed());
+            match result {
+                Ok((state, response)) => {
+                    info!(target: "rhubarb::access", "{} {} {} {:.3}ms",
+                        method, path, response.status().as_u16(), elapsed);
+                    Ok((state, response))
+                },
+                Err((state, err)) => {
+                    warn!(target: "rhubarb::access", "{} {} error {:.3}ms",
+                        method, path, elapsed);
+                    Err((state, err))
+                }
+            }
+        });
+        Box::new(future)
+    }
 }
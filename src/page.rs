@@ -0,0 +1,130 @@
+//! Additional named dashboards hosted by a single Rhubarb app.
+//!
+//! Each page has its own layout, state type, and update generator, and is served at
+//! `/dash/{name}`, with its graph endpoints at `/dash/{name}/graph` and `/dash/{name}/graph.svg`.
+
+use std::io;
+use std::panic::RefUnwindSafe;
+use std::sync::Arc;
+
+use gotham::state::State;
+use gotham::handler::{Handler, NewHandler, HandlerFuture};
+
+use handler::{GraphHandler, SvgHandler};
+use layout::Layout;
+use session::Sessions;
+use update::GenerateUpdate;
+use error::{self, RhubarbError};
+use ChartState;
+
+/// Path (relative to the app root) pages are served under.
+pub const PAGE_SCOPE: &str = "dash";
+
+/// A dashboard hosted by an app, with its state type erased so that pages with different state
+/// types can be served side by side.
+pub trait Page: Send + Sync + RefUnwindSafe {
+    /// Name of this page, used in its URL.
+    fn name(&self) -> &str;
+    /// Title of this page, shown in the page navigation.
+    fn title(&self) -> &str;
+    /// Handle a request to this page's graph endpoint, keeping a history of up to
+    /// `history_depth` states for each new session (if any).
+    fn handle_graph(&self, state: State, history_depth: Option<usize>) -> Box<HandlerFuture>;
+    /// Handle a request for this page's chart rendered as an SVG image.
+    fn handle_svg(&self, state: State) -> Box<HandlerFuture>;
+}
+
+/// Page serving a dashboard with a specific layout and update generator.
+pub struct Dashboard<St, Gen> {
+    name: String,
+    title: String,
+    layout: Arc<Layout<St>>,
+    sessions: Arc<Sessions<St>>,
+    gen: Gen,
+}
+impl<St, Gen: GenerateUpdate<St>> Dashboard<St, Gen> {
+    pub fn new<N: AsRef<str>, T: AsRef<str>>(name: N, title: T, layout: Layout<St>, gen: Gen)
+        -> Dashboard<St, Gen>
+    {
+        Dashboard {
+            name: name.as_ref().into(),
+            title: title.as_ref().into(),
+            layout: Arc::new(layout),
+            sessions: Arc::new(Sessions::new()),
+            gen,
+        }
+    }
+}
+impl<St, Gen> Page for Dashboard<St, Gen>
+    where St: ChartState, Gen: GenerateUpdate<St> + 'static
+{
+    fn name(&self) -> &str { &self.name }
+    fn title(&self) -> &str { &self.title }
+    fn handle_graph(&self, state: State, history_depth: Option<usize>) -> Box<HandlerFuture> {
+        let handler = GraphHandler::new(self.gen.clone(), self.layout.clone(),
+            self.sessions.clone());
+        match history_depth {
+            Some(depth) => handler.with_history(depth).handle(state),
+            None => handler.handle(state),
+        }
+    }
+    fn handle_svg(&self, state: State) -> Box<HandlerFuture> {
+        SvgHandler::new(self.gen.clone(), self.layout.clone()).handle(state)
+    }
+}
+
+/// URL of a page, relative to the app root.
+pub fn page_url(name: &str) -> String {
+    format!("{}/{}", PAGE_SCOPE, name)
+}
+
+/// Check that page names are unique and can be used as a URL path segment.
+pub(crate) fn validate_pages(pages: &[Arc<Page>]) -> error::Result<()> {
+    for (i, page) in pages.iter().enumerate() {
+        let name = page.name();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'
+            || c == '_')
+        {
+            return Err(RhubarbError::InvalidPage(format!(
+                "page name '{}' must be non-empty and only contain letters, digits, '-', or '_'",
+                name)));
+        }
+        if pages[..i].iter().any(|other| other.name() == name) {
+            return Err(RhubarbError::InvalidPage(format!("page name already in use: {}", name)));
+        }
+    }
+    Ok(())
+}
+
+/// Endpoints served by each page.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum PageEndpoint {
+    Graph { history_depth: Option<usize> },
+    Svg,
+}
+
+#[derive(Clone)]
+pub(crate) struct NewPageHandler {
+    page: Arc<Page>,
+    endpoint: PageEndpoint,
+}
+impl NewPageHandler {
+    pub(crate) fn new(page: Arc<Page>, endpoint: PageEndpoint) -> NewPageHandler {
+        NewPageHandler { page, endpoint }
+    }
+}
+impl NewHandler for NewPageHandler {
+    type Instance = NewPageHandler;
+
+    fn new_handler(&self) -> io::Result<NewPageHandler> {
+        Ok(self.clone())
+    }
+}
+impl Handler for NewPageHandler {
+    fn handle(self, state: State) -> Box<HandlerFuture> {
+        match self.endpoint {
+            PageEndpoint::Graph { history_depth } => self.page.handle_graph(state, history_depth),
+            PageEndpoint::Svg => self.page.handle_svg(state),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+/// Properties shared by every component (controls and panels).
+#[derive(Debug, Clone, Serialize)]
+pub struct CommonProps {
+    pub visible: bool,
+    pub enabled: bool,
+    /// Title of this component, used to label its tab or section when placed in a container.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tooltip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class: Option<String>,
+    /// Inline CSS style properties.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub style: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<Size>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<Size>,
+    /// Placement of this component when its parent is a grid panel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid: Option<GridPlacement>,
+}
+impl Default for CommonProps {
+    fn default() -> CommonProps {
+        CommonProps {
+            visible: true,
+            enabled: true,
+            title: None,
+            tooltip: None,
+            class: None,
+            style: BTreeMap::new(),
+            width: None,
+            height: None,
+            grid: None,
+        }
+    }
+}
+
+/// Access to (and builder methods for) the common properties of a component.
+pub trait CommonProperties {
+    fn common(&self) -> &CommonProps;
+    fn common_mut(&mut self) -> &mut CommonProps;
+
+    fn with_visible(mut self, visible: bool) -> Self where Self: Sized {
+        self.common_mut().visible = visible;
+        self
+    }
+    fn with_enabled(mut self, enabled: bool) -> Self where Self: Sized {
+        self.common_mut().enabled = enabled;
+        self
+    }
+    fn with_title<S: AsRef<str>>(mut self, title: S) -> Self where Self: Sized {
+        self.common_mut().title = Some(title.as_ref().into());
+        self
+    }
+    fn with_tooltip<S: AsRef<str>>(mut self, tooltip: S) -> Self where Self: Sized {
+        self.common_mut().tooltip = Some(tooltip.as_ref().into());
+        self
+    }
+    fn with_class<S: AsRef<str>>(mut self, class: S) -> Self where Self: Sized {
+        self.common_mut().class = Some(class.as_ref().into());
+        self
+    }
+    /// Set an inline CSS style property (e.g. `with_style("width", "200px")`).
+    fn with_style<P: AsRef<str>, V: AsRef<str>>(mut self, property: P, value: V) -> Self
+        where Self: Sized
+    {
+        self.common_mut().style.insert(property.as_ref().into(), value.as_ref().into());
+        self
+    }
+    fn with_width(mut self, width: Size) -> Self where Self: Sized {
+        self.common_mut().width = Some(width);
+        self
+    }
+    fn with_height(mut self, height: Size) -> Self where Self: Sized {
+        self.common_mut().height = Some(height);
+        self
+    }
+    fn with_grid_placement(mut self, placement: GridPlacement) -> Self where Self: Sized {
+        self.common_mut().grid = Some(placement);
+        self
+    }
+}
+
+/// Size of a component along one dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "unit", content = "value", rename_all = "lowercase")]
+pub enum Size {
+    /// Fixed size, in pixels.
+    Pixels(u32),
+    /// Percentage of the parent panel's size.
+    Percent(f64),
+    /// Share of the parent panel's remaining space, relative to its other flexible children.
+    Flex(f64),
+}
+
+/// Position (and span) of a component within a grid panel. Rows and columns are 1-based; if
+/// unspecified, the component is placed in the next free cell.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    pub row: Option<usize>,
+    pub column: Option<usize>,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+impl Default for GridPlacement {
+    fn default() -> GridPlacement {
+        GridPlacement { row: None, column: None, row_span: 1, column_span: 1 }
+    }
+}
+impl GridPlacement {
+    pub fn at(row: usize, column: usize) -> GridPlacement {
+        GridPlacement { row: Some(row), column: Some(column), ..GridPlacement::default() }
+    }
+    pub fn span(self, rows: usize, columns: usize) -> GridPlacement {
+        GridPlacement { row_span: rows, column_span: columns, ..self }
+    }
+}
+
+/// How a panel arranges its children.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PanelKind {
+    /// Children stacked vertically.
+    Column,
+    /// Children placed side by side.
+    Row,
+    /// Children placed in a CSS grid with the specified number of columns.
+    Grid { columns: usize },
+}
+impl Default for PanelKind {
+    fn default() -> PanelKind { PanelKind::Column }
+}
+
+/// Alignment of a panel's children, along (`justify`) or across (`align`) its main axis.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    Stretch,
+    SpaceBetween,
+    SpaceAround,
+}
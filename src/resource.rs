@@ -1,14 +1,44 @@
+use std::path::PathBuf;
+
+use serde_json::Value;
 
 #[derive(Serialize, Clone, Debug)]
 pub struct Script {
     pub url: String,
 }
 
-pub fn default_scripts() -> Vec<Script> {
+pub const DEFAULT_PLOTLY_VERSION: &str = "1.38.1";
+/// Route a locally-vendored plotly.js is served from.
+pub const LOCAL_PLOTLY_URL: &str = "vendor/plotly.min.js";
+
+/// Location the plotly.js library is loaded from.
+#[derive(Clone, Debug)]
+pub enum PlotlySource {
+    /// Load the specified version of plotly.js from the Plotly CDN.
+    Cdn { version: String },
+    /// Serve a vendored copy of plotly.js from the file at the specified path (for offline use).
+    Local(PathBuf),
+}
+impl Default for PlotlySource {
+    fn default() -> PlotlySource {
+        PlotlySource::Cdn { version: DEFAULT_PLOTLY_VERSION.into() }
+    }
+}
+impl PlotlySource {
+    pub fn url(&self) -> String {
+        match *self {
+            PlotlySource::Cdn { ref version } =>
+                format!("https://cdn.plot.ly/plotly-{}.min.js", version),
+            PlotlySource::Local(_) => LOCAL_PLOTLY_URL.into(),
+        }
+    }
+}
+
+pub fn default_scripts(plotly: &PlotlySource) -> Vec<Script> {
     vec![
-        "https://cdn.plot.ly/plotly-1.38.1.min.js",
-        "app_bundle/bundle.js"
-    ].iter().map(|s| Script { url: s.to_string() }).collect()
+        plotly.url(),
+        "app_bundle/bundle.js".into()
+    ].into_iter().map(|url| Script { url }).collect()
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -17,8 +47,101 @@ pub struct Style {
 }
 
 pub fn default_styles() -> Vec<Style> {
-    // vec![
-    //     "app_bundle/bundle.css"
-    // ].iter().map(|s| Style { url: s.to_string() }).collect()
-    vec![]
+    vec![
+        "app_bundle/bundle.css"
+    ].iter().map(|s| Style { url: s.to_string() }).collect()
+}
+
+/// Route of the graph endpoint of the main dashboard.
+pub const GRAPH_URL: &str = "graph";
+
+/// Configuration of the front-end bundle, inlined into dashboard pages as
+/// `window.RHUBARB_CONFIG` (see FRONTEND.md).
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendConfig {
+    /// Path the app is served under, with leading and trailing slashes.
+    pub base_url: String,
+    /// URL of the dashboard's graph endpoint, relative to `base_url`.
+    pub graph_url: String,
+    /// Shared state the page was requested with (see `share`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<Value>,
+}
+
+/// Link to one of the dashboards of a multi-page app, shown in the page navigation.
+#[derive(Serialize, Clone, Debug)]
+pub struct NavLink {
+    pub title: String,
+    /// URL of the dashboard, relative to the app root.
+    pub url: String,
+    /// Whether this link refers to the page it is shown on.
+    pub active: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Meta {
+    pub name: String,
+    pub content: String,
+}
+
+/// Source of a Handlebars template or partial.
+#[derive(Clone, Debug)]
+pub enum TemplateSource {
+    /// The default base template, loaded from the app's asset source.
+    Default,
+    File(PathBuf),
+    Inline(String),
+}
+
+/// Configuration of the index page served at the root of a Rhubarb app.
+#[derive(Clone, Debug)]
+pub struct PageConfig {
+    pub title: String,
+    /// Scripts loaded after the default (Plotly and front-end bundle) scripts.
+    pub scripts: Vec<Script>,
+    /// Stylesheets loaded after the default stylesheets.
+    pub styles: Vec<Style>,
+    pub meta: Vec<Meta>,
+    pub favicon: Option<String>,
+    pub plotly: PlotlySource,
+    pub template: TemplateSource,
+    pub partials: Vec<(String, TemplateSource)>,
+    /// Path the app is served under. Script, stylesheet, and graph URLs on the page are relative
+    /// to this path.
+    pub base_url: String,
+}
+impl Default for PageConfig {
+    fn default() -> PageConfig {
+        PageConfig {
+            title: "Graph!".into(),
+            scripts: vec![],
+            styles: vec![],
+            meta: vec![],
+            favicon: None,
+            plotly: PlotlySource::default(),
+            template: TemplateSource::Default,
+            partials: vec![],
+            base_url: "/".into(),
+        }
+    }
+}
+impl PageConfig {
+    /// Set the path the app is served under, adding leading and trailing slashes if missing.
+    pub fn set_base_url(&mut self, prefix: &str) {
+        let prefix = prefix.trim_matches('/');
+        self.base_url = if prefix.is_empty() { "/".into() } else { format!("/{}/", prefix) };
+    }
+    /// All scripts to include on the page, defaults first.
+    pub fn all_scripts(&self) -> Vec<Script> {
+        let mut scripts = default_scripts(&self.plotly);
+        scripts.extend(self.scripts.iter().cloned());
+        scripts
+    }
+    /// All stylesheets to include on the page, defaults first.
+    pub fn all_styles(&self) -> Vec<Style> {
+        let mut styles = default_styles();
+        styles.extend(self.styles.iter().cloned());
+        styles
+    }
 }
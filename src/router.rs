@@ -1,29 +1,93 @@
 use std::sync::Arc;
 
+use mime;
+
 use gotham::router::Router;
-use gotham::router::builder::{build_simple_router, DefineSingleRoute, DrawRoutes};
+use gotham::router::builder::{build_router, DefineSingleRoute, DrawRoutes};
+use gotham::pipeline::new_pipeline;
+use gotham::pipeline::single::single_pipeline;
 
 use handler;
+use logger::AccessLogger;
+use resource::{PageConfig, PlotlySource, NavLink, LOCAL_PLOTLY_URL, GRAPH_URL};
+use assets::{Asset, AssetSource, AssetKind};
+use error::{self, RhubarbError};
 use layout::Layout;
+use page::{Page, NewPageHandler, PageEndpoint, validate_pages, page_url};
 use update::GenerateUpdate;
 use hyper::{Get, Post};
 
 use ChartState;
 
-pub(crate) fn router<St, Gen>(gen: Gen, layout: Arc<Layout<St>>) -> Router
+pub(crate) fn router<St, Gen>(gen: Gen, layout: Arc<Layout<St>>, page: &PageConfig,
+    assets: &AssetSource, pages: &[Arc<Page>], history_depth: Option<usize>)
+    -> error::Result<Router>
     where Gen: GenerateUpdate<St> + 'static, St: ChartState
 {
-    build_simple_router(|route| {
-        route.get("/").to_new_handler(handler::NewIndexHandler::new());
+    validate_pages(pages)?;
+    // every dashboard links to the main dashboard and all other pages
+    let nav = |active: Option<&str>| -> Vec<NavLink> {
+        if pages.is_empty() {
+            return vec![];
+        }
+        let mut nav = vec![NavLink { title: page.title.clone(), url: "./".into(),
+            active: active.is_none() }];
+        nav.extend(pages.iter().map(|p| NavLink {
+            title: p.title().into(),
+            url: page_url(p.name()),
+            active: active == Some(p.name()),
+        }));
+        nav
+    };
+    let index_handler = handler::NewIndexHandler::for_page(page, assets, GRAPH_URL, &nav(None))?;
+    let mut page_index_handlers = vec![];
+    for p in pages {
+        let config = PageConfig { title: p.title().into(), ..page.clone() };
+        page_index_handlers.push(handler::NewIndexHandler::for_page(&config, assets,
+            &format!("{}/{}", page_url(p.name()), GRAPH_URL), &nav(Some(p.name())))?);
+    }
+    // load vendored plotly.js at startup, so a missing file is caught immediately
+    let plotly_handler = match page.plotly {
+        PlotlySource::Local(ref path) => {
+            let asset = Asset::from_file(path, mime::TEXT_JAVASCRIPT).map_err(|err| {
+                RhubarbError::MissingAsset { path: path.clone(), err }
+            })?;
+            Some(handler::NewAssetHandler::preloaded(asset))
+        },
+        PlotlySource::Cdn { .. } => None,
+    };
+    let svg_handler = handler::NewSvgHandler::new(gen.clone(), layout.clone());
+    let mut graph_handler = handler::NewGraphHandler::new(gen, layout);
+    if let Some(depth) = history_depth {
+        graph_handler = graph_handler.with_history(depth);
+    }
+    let (chain, pipelines) = single_pipeline(new_pipeline().add(AccessLogger).build());
+    Ok(build_router(chain, pipelines, |route| {
+        route.get("/").to_new_handler(index_handler);
         route.scope("/app_bundle", |route| {
-            route.get("/bundle.js").to(handler::app_bundle_js);
-            route.get("/bundle.css").to(handler::app_bundle_css);
+            route.get("/bundle.js").to_new_handler(
+                handler::NewAssetHandler::new(assets.clone(), AssetKind::BundleJs));
+            route.get("/bundle.css").to_new_handler(
+                handler::NewAssetHandler::new(assets.clone(), AssetKind::BundleCss));
         });
+        if let Some(plotly_handler) = plotly_handler {
+            route.get(&format!("/{}", LOCAL_PLOTLY_URL)).to_new_handler(plotly_handler);
+        }
         route.get("/test.json").to(handler::test_json);
+        route.get("/graph.svg").to_new_handler(svg_handler);
         route.associate("/graph", |assoc| {
-            assoc.request(vec![Get, Post]).to_new_handler(handler::NewGraphHandler::new(gen,
-                layout));
-            assoc.options().to(handler::options_origin);
+            assoc.request(vec![Get, Post]).to_new_handler(graph_handler);
         });
-    })
+        for (p, index_handler) in pages.iter().zip(page_index_handlers) {
+            let url = format!("/{}", page_url(p.name()));
+            route.get(&url).to_new_handler(index_handler);
+            route.get(&format!("{}/{}.svg", url, GRAPH_URL)).to_new_handler(
+                NewPageHandler::new(p.clone(), PageEndpoint::Svg));
+            let page_graph_handler = NewPageHandler::new(p.clone(),
+                PageEndpoint::Graph { history_depth });
+            route.associate(&format!("{}/{}", url, GRAPH_URL), |assoc| {
+                assoc.request(vec![Get, Post]).to_new_handler(page_graph_handler);
+            });
+        }
+    }))
 }
@@ -0,0 +1,132 @@
+//! Client sessions of a dashboard.
+//!
+//! Every client loading a dashboard starts a session, identified by a random token which is sent
+//! with the initial update (as `session`) and returned by the client with every event. Each
+//! session holds its own copy of the layout, so structural changes and component properties set
+//! by updates only ever affect the client which received them, along with the client's undo/redo
+//! history (see `history`).
+//!
+//! Sessions are only kept for long once the client sends its first event: until then, they're
+//! kept separately from the sessions of active clients, so a burst of page loads can only cause
+//! other unused sessions to be forgotten.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use base64;
+use linked_hash_map::LinkedHashMap;
+use rand::{thread_rng, RngCore};
+
+use layout::Layout;
+use history::History;
+use error::{self, RhubarbError};
+
+/// Maximum number of sessions of active clients (which have sent events) kept per dashboard; the
+/// least recently used are forgotten first.
+pub const MAX_SESSIONS: usize = 1024;
+/// Maximum number of new sessions (whose clients haven't sent any events yet) kept per dashboard;
+/// the oldest are forgotten first.
+pub const MAX_NEW_SESSIONS: usize = 1024;
+/// Number of random bytes in a session token.
+const TOKEN_BYTES: usize = 16;
+
+/// Server-side state of a single client.
+#[derive(Debug)]
+pub struct Session<St> {
+    /// The layout as last sent to the client.
+    pub layout: Layout<St>,
+    /// Undo/redo history of the client's state, if history is enabled.
+    pub history: Option<History<St>>,
+}
+impl<St> Session<St> {
+    /// New session, keeping a history of up to `history_depth` states (if any).
+    pub fn new(layout: Layout<St>, history_depth: Option<usize>) -> Session<St> {
+        Session {
+            layout,
+            history: history_depth.map(History::new),
+        }
+    }
+}
+
+/// Sessions of all clients of a dashboard.
+#[derive(Debug)]
+pub struct Sessions<St> {
+    sessions: Mutex<SessionMap<St>>,
+}
+type SharedSession<St> = Arc<Mutex<Session<St>>>;
+#[derive(Debug)]
+struct SessionMap<St> {
+    // sessions of clients which haven't sent any events, oldest first
+    new: LinkedHashMap<String, SharedSession<St>>,
+    // sessions of active clients, least recently used first
+    active: LinkedHashMap<String, SharedSession<St>>,
+}
+impl<St> Default for Sessions<St> {
+    fn default() -> Sessions<St> {
+        Sessions {
+            sessions: Mutex::new(SessionMap {
+                new: LinkedHashMap::new(),
+                active: LinkedHashMap::new(),
+            }),
+        }
+    }
+}
+impl<St> Sessions<St> {
+    pub fn new() -> Sessions<St> { Sessions::default() }
+
+    fn map(&self) -> MutexGuard<SessionMap<St>> {
+        // the map is only ever modified in place, so recover it if a request panicked
+        self.sessions.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    /// Store a new session under `token` (see `new_token`), forgetting the oldest new session if
+    /// there are too many. Sessions of active clients are unaffected.
+    pub fn insert(&self, token: String, session: Session<St>) {
+        let mut map = self.map();
+        if map.new.len() >= MAX_NEW_SESSIONS {
+            map.new.pop_front();
+        }
+        map.new.insert(token, Arc::new(Mutex::new(session)));
+    }
+    /// Session identified by `token`, marking it as the most recently used active session.
+    /// Unknown (or forgotten) sessions are rejected; clients can only obtain a session by
+    /// requesting an initial update.
+    pub fn get(&self, token: &str) -> error::Result<Arc<Mutex<Session<St>>>> {
+        let mut guard = self.map();
+        let map = &mut *guard;
+        if let Some(session) = map.active.get_refresh(token) {
+            return Ok(session.clone());
+        }
+        let session = match map.new.remove(token) {
+            Some(session) => session,
+            None => return Err(RhubarbError::InvalidSession(
+                "unknown or expired session; reload the dashboard to start a new one".into())),
+        };
+        if map.active.len() >= MAX_SESSIONS {
+            map.active.pop_front();
+        }
+        map.active.insert(token.into(), session.clone());
+        Ok(session)
+    }
+    /// Number of sessions currently kept.
+    pub fn len(&self) -> usize {
+        let map = self.map();
+        map.new.len() + map.active.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Lock a session for the remainder of a request, so that events from the same client are
+/// handled one at a time.
+pub fn lock<St>(session: &Mutex<Session<St>>) -> MutexGuard<Session<St>> {
+    // sessions are only modified once a request has been fully handled, so a panicking request
+    // can't leave a session half-updated
+    session.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// New random, unguessable session token.
+pub fn new_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    thread_rng().fill_bytes(&mut bytes);
+    base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD)
+}
@@ -0,0 +1,52 @@
+//! Encoding of dashboard state into shareable URLs.
+//!
+//! State is serialized as JSON and encoded as unpadded URL-safe base64, and passed in the `state`
+//! query parameter, e.g. `/?state=eyJ5ZWFyIjoxOTk1fQ`. Dashboard pages requested with this
+//! parameter pass it on to the front-end (see `NewIndexHandler::render`), and requests to the
+//! graph endpoint with it (and no event) start from the encoded state instead of `St::default()`.
+
+use base64;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use error::{self, RhubarbError};
+
+/// Name of the query parameter holding encoded state.
+pub const STATE_PARAM: &str = "state";
+
+/// Encode a state into a compact, URL-safe string.
+pub fn encode_state<St: Serialize>(state: &St) -> error::Result<String> {
+    let json = serde_json::to_vec(state)?;
+    Ok(base64::encode_config(&json, base64::URL_SAFE_NO_PAD))
+}
+
+/// Decode a state previously encoded with `encode_state`.
+pub fn decode_state<St: DeserializeOwned>(encoded: &str) -> error::Result<St> {
+    let json = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).map_err(|e| {
+        RhubarbError::InvalidState(format!("unable to decode state: {}", e))
+    })?;
+    serde_json::from_slice(&json).map_err(|e| {
+        RhubarbError::InvalidState(format!("unable to parse state: {}", e))
+    })
+}
+
+/// Encoded state in a URL query string, if present.
+pub fn state_param(query: &str) -> Option<&str> {
+    query.split('&').filter_map(|pair| {
+        let mut parts = pair.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(STATE_PARAM), Some(value)) if !value.is_empty() => Some(value),
+            _ => None,
+        }
+    }).next()
+}
+
+/// Initial state of a request with the specified query string: the encoded state, if present,
+/// or the default state otherwise.
+pub fn initial_state<St: DeserializeOwned + Default>(query: Option<&str>) -> error::Result<St> {
+    match query.and_then(state_param) {
+        Some(encoded) => decode_state(encoded),
+        None => Ok(St::default()),
+    }
+}
@@ -0,0 +1,170 @@
+//! Persistent storage of named dashboard states ("saved views").
+//!
+//! States are stored as JSON values, so a single store can hold views of dashboards with
+//! different state types. Use `save_view` and `load_view` to store and restore typed states, or
+//! the `SaveViewControl` and `LoadViewControl` controls to let users do so from the dashboard.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::panic::RefUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{self, Value};
+
+use error::{self, RhubarbError};
+
+/// Storage of named dashboard states.
+pub trait ViewStore: Send + Sync + RefUnwindSafe {
+    /// Save a state under `name`, replacing any existing view with that name.
+    fn save(&self, name: &str, state: Value) -> error::Result<()>;
+    /// State saved under `name`, if any.
+    fn load(&self, name: &str) -> error::Result<Option<Value>>;
+    /// Names of all saved views, in alphabetical order.
+    fn names(&self) -> error::Result<Vec<String>>;
+    /// Remove the view saved under `name`. Returns whether the view existed.
+    fn remove(&self, name: &str) -> error::Result<bool>;
+}
+
+/// Save a state under `name`. View names must not be empty.
+pub fn save_view<St: Serialize>(store: &ViewStore, name: &str, state: &St) -> error::Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(RhubarbError::InvalidView("view name must not be empty".into()));
+    }
+    store.save(name, serde_json::to_value(state)?)
+}
+
+/// Restore the state saved under `name`.
+pub fn load_view<St: DeserializeOwned>(store: &ViewStore, name: &str) -> error::Result<St> {
+    let name = name.trim();
+    match store.load(name)? {
+        Some(state) => serde_json::from_value(state).map_err(|e| {
+            RhubarbError::InvalidView(format!("unable to restore view '{}': {}", name, e))
+        }),
+        None => Err(RhubarbError::InvalidView(format!("no saved view named '{}'", name))),
+    }
+}
+
+fn store_err<E: ToString>(err: E) -> RhubarbError {
+    RhubarbError::ViewStore(err.to_string())
+}
+
+/// View store keeping all views in a single JSON file, which is created when the first view is
+/// saved.
+#[derive(Debug)]
+pub struct JsonFileStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+impl JsonFileStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> JsonFileStore {
+        JsonFileStore {
+            path: path.as_ref().into(),
+            lock: Mutex::new(()),
+        }
+    }
+    fn lock(&self) -> error::Result<MutexGuard<()>> {
+        self.lock.lock().map_err(|_| store_err("view store lock poisoned"))
+    }
+    fn read(&self) -> error::Result<BTreeMap<String, Value>> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let file = File::open(&self.path).map_err(store_err)?;
+        serde_json::from_reader(BufReader::new(file)).map_err(store_err)
+    }
+    fn write(&self, views: &BTreeMap<String, Value>) -> error::Result<()> {
+        // write to a temporary file first, so a failed write doesn't lose existing views
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path).map_err(store_err)?);
+            serde_json::to_writer_pretty(&mut writer, views).map_err(store_err)?;
+            // make sure the views are on disk before replacing the old file
+            writer.flush().map_err(store_err)?;
+            writer.get_ref().sync_all().map_err(store_err)?;
+        }
+        fs::rename(&tmp_path, &self.path).map_err(store_err)
+    }
+}
+impl ViewStore for JsonFileStore {
+    fn save(&self, name: &str, state: Value) -> error::Result<()> {
+        let _guard = self.lock()?;
+        let mut views = self.read()?;
+        views.insert(name.into(), state);
+        self.write(&views)
+    }
+    fn load(&self, name: &str) -> error::Result<Option<Value>> {
+        let _guard = self.lock()?;
+        Ok(self.read()?.remove(name))
+    }
+    fn names(&self) -> error::Result<Vec<String>> {
+        let _guard = self.lock()?;
+        Ok(self.read()?.keys().cloned().collect())
+    }
+    fn remove(&self, name: &str) -> error::Result<bool> {
+        let _guard = self.lock()?;
+        let mut views = self.read()?;
+        let existed = views.remove(name).is_some();
+        if existed {
+            self.write(&views)?;
+        }
+        Ok(existed)
+    }
+}
+
+/// View store backed by an embedded SQLite database. Only available with the `sqlite` feature.
+#[cfg(feature = "sqlite")]
+pub struct SqliteStore {
+    conn: Mutex<::rusqlite::Connection>,
+}
+#[cfg(feature = "sqlite")]
+impl SqliteStore {
+    /// Open (or create) the database at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> error::Result<SqliteStore> {
+        let conn = ::rusqlite::Connection::open(path).map_err(store_err)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS views (name TEXT PRIMARY KEY, state TEXT NOT NULL)",
+            &[]
+        ).map_err(store_err)?;
+        Ok(SqliteStore { conn: Mutex::new(conn) })
+    }
+    fn conn(&self) -> error::Result<MutexGuard<::rusqlite::Connection>> {
+        self.conn.lock().map_err(|_| store_err("view store lock poisoned"))
+    }
+}
+#[cfg(feature = "sqlite")]
+impl ViewStore for SqliteStore {
+    fn save(&self, name: &str, state: Value) -> error::Result<()> {
+        let name = name.to_string();
+        let state = serde_json::to_string(&state)?;
+        self.conn()?.execute("INSERT OR REPLACE INTO views (name, state) VALUES (?1, ?2)",
+            &[&name, &state]).map_err(store_err)?;
+        Ok(())
+    }
+    fn load(&self, name: &str) -> error::Result<Option<Value>> {
+        let name = name.to_string();
+        let state = self.conn()?.query_row("SELECT state FROM views WHERE name = ?1", &[&name],
+            |row| row.get::<_, String>(0));
+        match state {
+            Ok(state) => Ok(Some(serde_json::from_str(&state)?)),
+            Err(::rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(store_err(e)),
+        }
+    }
+    fn names(&self) -> error::Result<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare("SELECT name FROM views ORDER BY name").map_err(store_err)?;
+        let names = stmt.query_map(&[], |row| row.get::<_, String>(0)).map_err(store_err)?;
+        names.collect::<Result<Vec<_>, _>>().map_err(store_err)
+    }
+    fn remove(&self, name: &str) -> error::Result<bool> {
+        let name = name.to_string();
+        let removed = self.conn()?.execute("DELETE FROM views WHERE name = ?1", &[&name])
+            .map_err(store_err)?;
+        Ok(removed > 0)
+    }
+}
@@ -1,18 +1,28 @@
 use std::panic::RefUnwindSafe;
 
 use serde::{Serialize, Serializer};
-use serde::ser::SerializeStruct;
+use serde::ser::{self, SerializeStruct};
 
 use {Chart, ChartState};
-use layout::{Component, IndexedComponent, ComponentIndex};
+use layout::{Component, IndexedComponent, ComponentIndex, LayoutChange, LayoutDiff, Panel};
+use control::Control;
+use container::Container;
+use chart::IndexedChart;
+use props::CommonProperties;
 use event::EventMessage;
 use error;
 use layout::Layout;
+use share::encode_state;
+use history::HistoryStatus;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClientMessage<St> {
     pub ui_state: St,
     pub event_message: EventMessage,
+    /// Session token received with the initial update. Clients without a session can't receive
+    /// structural layout changes.
+    #[serde(default)]
+    pub session: Option<String>,
 }
 
 pub struct LayoutUpdate<'a, St: 'a> {
@@ -32,26 +42,45 @@ impl<'a, St: ChartState> Serialize for LayoutUpdate<'a, St> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         let mut state = match self.update.chart {
             Some(ref chart) => {
-                let mut state = serializer.serialize_struct("Update", 4)?;
+                let mut state = serializer.serialize_struct("Update",
+                    6 + self.update.session_fields())?;
                 state.serialize_field("chart", &chart)?;
                 state
             },
             None => {
-                serializer.serialize_struct("Update", 3)?
+                serializer.serialize_struct("Update", 5 + self.update.session_fields())?
             }
         };
+        state.serialize_field("charts", &self.update.charts)?;
         state.serialize_field("layout", &self.layout)?;
         state.serialize_field("components", &self.update.components)?;
         state.serialize_field("state", &self.update.state)?;
+        state.serialize_field("share", &self.update.share_token().map_err(ser::Error::custom)?)?;
+        if let Some(ref session) = self.update.session {
+            state.serialize_field("session", session)?;
+        }
+        if let Some(ref history) = self.update.history {
+            state.serialize_field("history", history)?;
+        }
         state.end()
     }
 }
 
 
 pub struct Update<St> {
+    // chart shown outside of the layout
     chart: Option<Chart>,
+    // figures for chart components in layout
+    charts: Vec<IndexedChart>,
     // updates to referenced values in layout
     components: Vec<IndexedComponent<St>>,
+    // structural changes to the layout, not yet applied
+    layout_changes: Vec<LayoutChange<St>>,
+    // structural changes which have been applied to the layout, communicated to client
+    layout_diff: Vec<LayoutDiff<St>>,
+    // session token of the client, and its state history status if history is enabled
+    session: Option<String>,
+    history: Option<HistoryStatus>,
     // state communicated to client
     state: St
 }
@@ -59,13 +88,41 @@ impl<St> Update<St> {
     pub fn new<C: Into<Option<Chart>>>(chart: C, state: St) -> Update<St> {
         Update {
             chart: chart.into(),
+            charts: vec![],
             components: vec![],
+            layout_changes: vec![],
+            layout_diff: vec![],
+            session: None,
+            history: None,
             state
         }
     }
     pub fn set_chart(&mut self, chart: Chart) {
         self.chart = Some(chart);
     }
+    pub fn chart(&self) -> Option<&Chart> {
+        self.chart.as_ref()
+    }
+    /// Set (or replace) the figure of the chart component at `chart_idx`.
+    pub fn set_chart_at(&mut self, chart_idx: ComponentIndex, chart: Chart) {
+        self.charts.retain(|indexed| indexed.idx != chart_idx);
+        self.charts.push(IndexedChart { idx: chart_idx, chart });
+    }
+    /// Figure for the chart component at `chart_idx`, if this update replaces it.
+    pub fn chart_at(&self, chart_idx: ComponentIndex) -> Option<&Chart> {
+        self.charts.iter().find(|indexed| indexed.idx == chart_idx).map(|indexed| &indexed.chart)
+    }
+    /// Figures for chart components in this update, along with their indices.
+    pub fn indexed_charts(&self) -> &[IndexedChart] {
+        &self.charts
+    }
+    /// All chart figures in this update, starting with the chart shown outside of the layout.
+    pub fn charts(&self) -> Vec<&Chart> {
+        self.chart.iter().chain(self.charts.iter().map(|indexed| &indexed.chart)).collect()
+    }
+    /// Replace the component at `component_idx` on the client. Its common properties (such as
+    /// visibility and enabled state) are also kept in the client's session, so events from
+    /// components disabled this way are rejected for that client only.
     pub fn add_component<C: Into<Component<St>>>(&mut self, component_idx: ComponentIndex,
         updated_component: C) -> error::Result<()>
     {
@@ -75,25 +132,168 @@ impl<St> Update<St> {
         });
         Ok(())
     }
+    /// Replace the component at `component_idx` on the client with a copy of its current version
+    /// in `layout`, modified by `edit`.
+    pub fn edit_component<F>(&mut self, layout: &Layout<St>, component_idx: ComponentIndex,
+        edit: F) -> error::Result<()>
+        where St: Clone, F: FnOnce(&mut Component<St>) -> error::Result<()>
+    {
+        let mut component = match layout.get(component_idx) {
+            Some(component) => component.clone(),
+            None => return Err(error::RhubarbError::UnknownComponent(component_idx)),
+        };
+        edit(&mut component)?;
+        self.add_component(component_idx, component)
+    }
+    /// Open (or close) the modal at `modal_idx`.
+    pub fn set_modal_open(&mut self, layout: &Layout<St>, modal_idx: ComponentIndex, open: bool)
+        -> error::Result<()>
+        where St: Clone
+    {
+        self.edit_component(layout, modal_idx, |component| match *component {
+            Component::Container(Container::Modal(ref mut modal)) => {
+                modal.open = open;
+                Ok(())
+            },
+            _ => Err(wrong_kind(modal_idx, "modal")),
+        })
+    }
+    /// Start (or stop) the timer at `timer_idx`.
+    pub fn set_timer_running(&mut self, layout: &Layout<St>, timer_idx: ComponentIndex,
+        running: bool) -> error::Result<()>
+        where St: Clone
+    {
+        self.edit_component(layout, timer_idx, |component| match *component {
+            Component::Control(Control::Timer(ref mut timer)) => {
+                timer.set_running(running);
+                Ok(())
+            },
+            _ => Err(wrong_kind(timer_idx, "timer")),
+        })
+    }
+    /// Add a new panel to the layout, within `parent` (or at the top level).
+    pub fn add_panel<S: AsRef<str>>(&mut self, name: S, parent: Option<ComponentIndex>,
+        position: Option<usize>)
+    {
+        self.add_panel_with(name, Panel::new(), parent, position);
+    }
+    /// Add a new panel with the specified kind and properties to the layout.
+    pub fn add_panel_with<S: AsRef<str>>(&mut self, name: S, panel: Panel,
+        parent: Option<ComponentIndex>, position: Option<usize>)
+    {
+        self.layout_changes.push(LayoutChange::AddPanel {
+            name: name.as_ref().into(),
+            panel,
+            parent,
+            position
+        });
+    }
+    /// Add a new control to a panel in the layout.
+    pub fn add_control<S: AsRef<str>, C: Into<Control<St>>>(&mut self, name: S, control: C,
+        panel: ComponentIndex, position: Option<usize>)
+    {
+        self.layout_changes.push(LayoutChange::AddControl {
+            name: name.as_ref().into(),
+            control: control.into(),
+            panel,
+            position
+        });
+    }
+    /// Remove a component (and any descendants) from the layout.
+    pub fn remove_component(&mut self, component_idx: ComponentIndex) {
+        self.layout_changes.push(LayoutChange::Remove(component_idx));
+    }
+    /// Move a component to a new position, possibly within a different parent panel.
+    pub fn move_component(&mut self, component_idx: ComponentIndex,
+        parent: Option<ComponentIndex>, position: usize)
+    {
+        self.layout_changes.push(LayoutChange::Move { idx: component_idx, parent, position });
+    }
+    /// Attach the client's session token.
+    pub fn set_session(&mut self, session: String) {
+        self.session = Some(session);
+    }
+    pub fn session(&self) -> Option<&str> {
+        self.session.as_ref().map(|session| session.as_str())
+    }
+    /// Attach the status of the client's state history.
+    pub fn set_history(&mut self, history: HistoryStatus) {
+        self.history = Some(history);
+    }
+    fn session_fields(&self) -> usize {
+        self.session.is_some() as usize + self.history.is_some() as usize
+    }
+    /// Whether this update makes structural changes to the layout. Updated components don't
+    /// count: their common properties only apply to the session receiving them.
+    pub fn modifies_layout(&self) -> bool {
+        !self.layout_changes.is_empty()
+    }
+    /// Apply this update's structural changes to a session's `layout`, in the order they were
+    /// added. Either all changes are applied or, if any change fails, none are. Changes which
+    /// have already been made (adding a component whose name is in use, or removing a component
+    /// which has already been removed) are skipped, so generators may request the same changes
+    /// on every update. Common properties (such as enabled state) of updated components are also
+    /// copied into `layout`.
+    pub fn apply_layout_changes(&mut self, layout: &mut Layout<St>) -> error::Result<()>
+        where St: Clone
+    {
+        if !self.layout_changes.is_empty() {
+            // stage the changes on a copy, so a failing change leaves `layout` untouched
+            let mut staged = layout.clone();
+            let mut diff = vec![];
+            for change in &self.layout_changes {
+                if !change.is_applied(&staged) {
+                    diff.push(staged.apply_change(change.clone())?);
+                }
+            }
+            *layout = staged;
+            self.layout_changes.clear();
+            self.layout_diff.extend(diff);
+        }
+        for updated in &self.components {
+            if let Some(component) = layout.get_mut(updated.idx) {
+                *component.common_mut() = updated.component.common().clone();
+            }
+        }
+        Ok(())
+    }
+}
+impl<St: ChartState> Update<St> {
+    /// State of this update encoded for use in a shareable URL (see `share`).
+    pub fn share_token(&self) -> error::Result<String> {
+        encode_state(&self.state)
+    }
 }
 impl<St: ChartState> Serialize for Update<St> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        let mut state = match self.chart {
-            Some(ref chart) => {
-                let mut state = serializer.serialize_struct("Update", 2)?;
-                state.serialize_field("chart", &chart)?;
-                state
-            },
-            None => {
-                serializer.serialize_struct("Update", 1)?
-            }
-        };
+        let num_fields = 4 + self.chart.is_some() as usize
+            + !self.layout_diff.is_empty() as usize + self.session_fields();
+        let mut state = serializer.serialize_struct("Update", num_fields)?;
+        if let Some(ref chart) = self.chart {
+            state.serialize_field("chart", &chart)?;
+        }
+        state.serialize_field("charts", &self.charts)?;
         state.serialize_field("components", &self.components)?;
+        if !self.layout_diff.is_empty() {
+            state.serialize_field("layout_diff", &self.layout_diff)?;
+        }
         state.serialize_field("state", &self.state)?;
+        state.serialize_field("share", &self.share_token().map_err(ser::Error::custom)?)?;
+        if let Some(ref session) = self.session {
+            state.serialize_field("session", session)?;
+        }
+        if let Some(ref history) = self.history {
+            state.serialize_field("history", history)?;
+        }
         state.end()
     }
 }
 
+fn wrong_kind(component_idx: ComponentIndex, kind: &str) -> error::RhubarbError {
+    error::RhubarbError::InvalidLayout(
+        format!("component with index {} is not a {}", component_idx, kind))
+}
+
 pub trait GenerateUpdate<St>: Send + Sync + RefUnwindSafe + Clone {
     fn update(&self, layout: &Layout<St>, prev_state: Option<St>, state: St)
         -> error::Result<Update<St>>;
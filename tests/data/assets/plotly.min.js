@@ -0,0 +1 @@
+// stand-in for a vendored copy of plotly.js
@@ -0,0 +1,94 @@
+extern crate rhubarb;
+
+use std::path::PathBuf;
+
+use rhubarb::definition::{load_layout, HandlerRegistry};
+use rhubarb::control::button::ButtonClick;
+use rhubarb::control::slider::SliderChange;
+use rhubarb::error::RhubarbError;
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/definitions").join(name)
+}
+
+fn handlers() -> HandlerRegistry<u32> {
+    HandlerRegistry::new()
+        .on_change("set_year", |change: SliderChange, _: u32| Ok(change.idx as u32))
+        .on_click("reset", |_: ButtonClick, _: u32| Ok(0))
+}
+
+fn definition_errors(name: &str) -> Vec<(Option<usize>, String)> {
+    match load_layout(fixture(name), &handlers()) {
+        Err(RhubarbError::Definition(errors)) => {
+            errors.into_iter().map(|err| (err.line, err.message)).collect()
+        },
+        other => panic!("expected definition errors for {}, got {:?}", name, other),
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "yaml"))]
+fn assert_valid(name: &str) {
+    let layout = load_layout(fixture(name), &handlers()).unwrap();
+    let main = layout.get_component_index("main").unwrap();
+    let year = layout.get_component_index("year").unwrap();
+    let reset = layout.get_component_index("reset").unwrap();
+    assert_eq!(layout.children(Some(main)).unwrap(), &[year, reset]);
+}
+
+#[cfg(any(feature = "toml", feature = "yaml"))]
+fn assert_duplicate_name(name: &str, line: usize) {
+    let errors = definition_errors(name);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, Some(line));
+    assert!(errors[0].1.contains("duplicate component name 'reset'"), "{}", errors[0].1);
+    assert!(errors[0].1.contains("'inner'"), "{}", errors[0].1);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn valid_toml_definition_loads() {
+    assert_valid("valid.toml");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn valid_yaml_definition_loads() {
+    assert_valid("valid.yaml");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn unknown_handler_rejected() {
+    let errors = definition_errors("unknown_handler.toml");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, Some(8));
+    assert!(errors[0].1.contains("'not_registered'"), "{}", errors[0].1);
+    assert!(errors[0].1.contains("'reset'"), "{}", errors[0].1);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn duplicate_toml_name_rejected() {
+    assert_duplicate_name("duplicate_name.toml", 14);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn duplicate_yaml_name_rejected() {
+    assert_duplicate_name("duplicate_name.yaml", 11);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn syntax_errors_report_line() {
+    let errors = definition_errors("invalid_syntax.toml");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, Some(2));
+}
+
+#[test]
+fn unreadable_file_is_definition_error() {
+    let errors = definition_errors("missing.toml");
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].1.contains("missing.toml"), "{}", errors[0].1);
+}
@@ -0,0 +1,37 @@
+extern crate rhubarb;
+extern crate rhubarb_graph as rg;
+
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+use rhubarb::Chart;
+use rhubarb::export::to_html;
+use rhubarb::resource::PlotlySource;
+use rhubarb::error::RhubarbError;
+
+fn sample_chart() -> Chart {
+    Chart::Scatter(rg::Graph::new(vec![], rg::Layout::default().title("Empty")))
+}
+
+#[test]
+fn export_inlines_plotly() {
+    let path = env::temp_dir().join(format!("rhubarb-export-{}.js", std::process::id()));
+    File::create(&path).unwrap().write_all(b"var Plotly = {}; // </script> inside").unwrap();
+
+    let html = to_html(&sample_chart(), &PlotlySource::Local(path.clone())).unwrap();
+    assert!(html.contains("var Plotly = {};"));
+    assert!(html.contains("<\\/script> inside"));
+    assert!(html.contains("Plotly.newPlot('chart'"));
+    assert!(!html.contains("<script src="));
+    assert!(!html.contains("cdn.plot.ly"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn export_requires_local_plotly() {
+    match to_html(&sample_chart(), &PlotlySource::default()) {
+        Err(RhubarbError::Export(_)) => {},
+        other => panic!("expected export error, got {:?}", other),
+    }
+}
@@ -0,0 +1,83 @@
+extern crate rhubarb;
+extern crate gotham;
+extern crate hyper;
+extern crate mime;
+#[macro_use]
+extern crate serde_json;
+
+use std::sync::Arc;
+
+use gotham::test::TestServer;
+use hyper::StatusCode;
+use hyper::header::AccessControlAllowOrigin;
+use serde_json::Value;
+
+use rhubarb::Chart;
+use rhubarb::control::button::ButtonControl;
+use rhubarb::error;
+use rhubarb::handler::NewGraphHandler;
+use rhubarb::layout::Layout;
+use rhubarb::props::CommonProperties;
+use rhubarb::update::Update;
+
+fn generate(_: &Layout<u32>, _: Option<u32>, state: u32) -> error::Result<Update<u32>> {
+    Ok(Update::new(None::<Chart>, state))
+}
+
+fn test_server() -> (TestServer, usize, usize) {
+    let mut layout = Layout::new();
+    let panel = layout.add_panel("main", None).unwrap();
+    let go = layout.add_control_to_panel("go", ButtonControl::new("Go"), panel).unwrap();
+    let stop = layout.add_control_to_panel("stop",
+        ButtonControl::new("Stop").with_enabled(false), panel).unwrap();
+    let handler = NewGraphHandler::new(generate, Arc::new(layout));
+    (TestServer::new(handler).unwrap(), go, stop)
+}
+
+/// Post `event` for component `idx` (without a session), returning the response status and body.
+fn post_event(server: &TestServer, idx: usize, event: Value) -> (StatusCode, Value) {
+    let body = json!({ "ui_state": 0, "event_message": { "idx": idx, "event": event } });
+    let response = server.client()
+        .post("http://localhost/graph", body.to_string(), mime::APPLICATION_JSON)
+        .perform()
+        .unwrap();
+    let status = response.status();
+    // graph requests are only served to pages from the same origin
+    assert!(response.headers().get::<AccessControlAllowOrigin>().is_none());
+    (status, serde_json::from_slice(&response.read_body().unwrap()).unwrap())
+}
+
+#[test]
+fn events_handled() {
+    let (server, go, _) = test_server();
+    let (status, body) = post_event(&server, go, json!({ "ButtonClick": {} }));
+    assert_eq!(status, StatusCode::Ok);
+    assert_eq!(body["state"], json!(0));
+}
+
+#[test]
+fn unknown_component_rejected() {
+    let (server, _, _) = test_server();
+    let (status, body) = post_event(&server, 42, json!({ "ButtonClick": {} }));
+    assert_eq!(status, StatusCode::BadRequest);
+    assert_eq!(body["kind"], json!("UnknownComponent"));
+    assert_eq!(body["component"], json!(42));
+}
+
+#[test]
+fn invalid_event_rejected() {
+    let (server, go, _) = test_server();
+    let (status, body) = post_event(&server, go, json!({ "SliderChange": { "idx": 1 } }));
+    assert_eq!(status, StatusCode::UnprocessableEntity);
+    assert_eq!(body["kind"], json!("InvalidEvent"));
+    assert_eq!(body["component"], json!(go));
+}
+
+#[test]
+fn disabled_component_rejected() {
+    let (server, _, stop) = test_server();
+    let (status, body) = post_event(&server, stop, json!({ "ButtonClick": {} }));
+    assert_eq!(status, StatusCode::UnprocessableEntity);
+    assert_eq!(body["kind"], json!("DisabledComponent"));
+    assert_eq!(body["component"], json!(stop));
+}
@@ -0,0 +1,58 @@
+extern crate rhubarb;
+
+use rhubarb::history::{History, HistoryAction, HistoryStatus};
+
+#[test]
+fn undo_redo_order() {
+    let mut history = History::new(10);
+    history.record(1);
+    history.record(2);
+    // current state is 3
+    assert_eq!(history.undo(3), Some(2));
+    assert_eq!(history.undo(2), Some(1));
+    assert_eq!(history.undo(1), None);
+    assert_eq!(history.redo(1), Some(2));
+    assert_eq!(history.redo(2), Some(3));
+    assert_eq!(history.redo(3), None);
+}
+
+#[test]
+fn record_clears_redo() {
+    let mut history = History::new(10);
+    history.record(1);
+    assert_eq!(history.undo(2), Some(1));
+    assert_eq!(history.status(), HistoryStatus { can_undo: false, can_redo: true });
+    history.record(1);
+    assert_eq!(history.status(), HistoryStatus { can_undo: true, can_redo: false });
+    assert_eq!(history.redo(4), None);
+}
+
+#[test]
+fn depth_bounds() {
+    let mut history = History::new(3);
+    for state in 0..5 {
+        history.record(state);
+    }
+    assert_eq!(history.undo_len(), 3);
+    // the oldest states are forgotten first
+    assert_eq!(history.undo(5), Some(4));
+    assert_eq!(history.undo(4), Some(3));
+    assert_eq!(history.undo(3), Some(2));
+    assert_eq!(history.undo(2), None);
+
+    let mut history = History::new(0);
+    history.record(1);
+    assert_eq!(history.undo_len(), 0);
+    assert_eq!(history.status(), HistoryStatus { can_undo: false, can_redo: false });
+}
+
+#[test]
+fn step_and_record_change() {
+    let mut history = History::new(10);
+    history.record_change(1, &1).unwrap();
+    assert_eq!(history.undo_len(), 0);
+    history.record_change(1, &2).unwrap();
+    assert_eq!(history.step(HistoryAction::Undo, 2), 1);
+    assert_eq!(history.step(HistoryAction::Undo, 1), 1);
+    assert_eq!(history.step(HistoryAction::Redo, 1), 2);
+}
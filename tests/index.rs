@@ -0,0 +1,135 @@
+extern crate rhubarb;
+extern crate serde_json;
+
+use std::path::PathBuf;
+
+use rhubarb::handler::NewIndexHandler;
+use rhubarb::resource::{PageConfig, PlotlySource, NavLink, Script, Style, TemplateSource};
+use rhubarb::assets::AssetSource;
+use rhubarb::page::page_url;
+use rhubarb::share::encode_state;
+use rhubarb::error::RhubarbError;
+
+fn attributes<'a>(page: &'a str, name: &str) -> Vec<&'a str> {
+    let pattern = format!("{}=\"", name);
+    page.match_indices(pattern.as_str()).map(|(start, _)| {
+        let value = &page[start + pattern.len()..];
+        &value[..value.find('"').unwrap()]
+    }).collect()
+}
+
+fn frontend_config(page: &str) -> serde_json::Value {
+    let start = page.find("window.RHUBARB_CONFIG = ").unwrap() + "window.RHUBARB_CONFIG = ".len();
+    let end = start + page[start..].find(";</script>").unwrap();
+    serde_json::from_str(&page[start..end]).unwrap()
+}
+
+#[test]
+fn index_urls_under_prefix() {
+    let mut config = PageConfig::default();
+    config.plotly = PlotlySource::Local("plotly.min.js".into());
+    config.set_base_url("reports");
+    let nav = vec![
+        NavLink { title: "Main".into(), url: "./".into(), active: false },
+        NavLink { title: "Sales".into(), url: page_url("sales"), active: true },
+    ];
+    let graph_url = format!("{}/graph", page_url("sales"));
+    let handler = NewIndexHandler::for_page(&config, &AssetSource::default(), &graph_url, &nav)
+        .unwrap();
+    let page = &handler.render(None).unwrap();
+
+    assert_eq!(attributes(page, "<base href"), vec!["/reports/"]);
+    let config = frontend_config(page);
+    assert_eq!(config["baseUrl"], "/reports/");
+    assert_eq!(config["graphUrl"], graph_url.as_str());
+
+    // every other URL is relative, so it resolves under the base URL
+    let mut urls = attributes(page, "<link rel=\"stylesheet\" href");
+    urls.extend(attributes(page, "<a href"));
+    urls.extend(attributes(page, "<script src"));
+    urls.push(config["graphUrl"].as_str().unwrap());
+    // stylesheet, two navigation links, plotly and the bundle, and the graph endpoint
+    assert_eq!(urls.len(), 6, "{:?}", urls);
+    for url in urls {
+        assert!(!url.starts_with('/') && !url.contains("://"), "absolute URL {}", url);
+    }
+}
+
+#[test]
+fn index_passes_shared_state() {
+    let handler = NewIndexHandler::new(&PageConfig::default(), &AssetSource::default()).unwrap();
+    let config = frontend_config(&handler.render(Some("x=1")).unwrap());
+    assert_eq!(config["graphUrl"], "graph");
+    assert!(config.get("state").is_none());
+
+    let token = encode_state(&(1995u32, "</script>")).unwrap();
+    let page = handler.render(Some(&format!("x=1&state={}", token))).unwrap();
+    assert!(!page.contains("\"</script>"));
+    let config = frontend_config(&page);
+    assert_eq!(config["graphUrl"], format!("graph?state={}", token).as_str());
+    assert_eq!(config["state"], json_value(r#"[1995, "</script>"]"#));
+
+    match handler.render(Some("state=not-a-state")) {
+        Err(RhubarbError::InvalidState(_)) => {},
+        other => panic!("expected invalid state error, got {:?}", other),
+    }
+}
+
+fn template(name: &str) -> TemplateSource {
+    TemplateSource::File(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/templates")
+        .join(name))
+}
+
+#[test]
+fn custom_template_rendered() {
+    let mut config = PageConfig::default();
+    config.title = "Sales".into();
+    config.scripts.push(Script { url: "extra.js".into() });
+    config.styles.push(Style { url: "extra.css".into() });
+    config.template = template("custom.hbs");
+    config.partials.push(("footer".into(), template("footer.hbs")));
+    let handler = NewIndexHandler::new(&config, &AssetSource::default()).unwrap();
+    let page = handler.render(None).unwrap();
+
+    assert!(page.contains("<title>Custom: Sales</title>"), "{}", page);
+    assert!(page.contains("<footer>Sales footer</footer>"), "{}", page);
+    // custom scripts and styles are loaded after the default ones
+    let scripts = attributes(&page, "<script src");
+    assert_eq!(scripts.last(), Some(&"extra.js"));
+    assert!(scripts.len() > 1);
+    let styles = attributes(&page, "<link rel=\"stylesheet\" href");
+    assert_eq!(styles.last(), Some(&"extra.css"));
+    assert!(styles.len() > 1);
+    assert_eq!(frontend_config(&page)["graphUrl"], "graph");
+}
+
+#[test]
+fn missing_template_rejected() {
+    let mut config = PageConfig::default();
+    config.template = template("missing.hbs");
+    match NewIndexHandler::new(&config, &AssetSource::default()) {
+        Err(RhubarbError::Template(_)) => {},
+        other => panic!("expected template error, got {:?}", other),
+    }
+
+    let mut config = PageConfig::default();
+    config.partials.push(("footer".into(), template("missing.hbs")));
+    match NewIndexHandler::new(&config, &AssetSource::default()) {
+        Err(RhubarbError::Template(_)) => {},
+        other => panic!("expected template error, got {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_template_rejected() {
+    let mut config = PageConfig::default();
+    config.template = template("invalid.hbs");
+    match NewIndexHandler::new(&config, &AssetSource::default()) {
+        Err(RhubarbError::Template(_)) => {},
+        other => panic!("expected template error, got {:?}", other),
+    }
+}
+
+fn json_value(s: &str) -> serde_json::Value {
+    serde_json::from_str(s).unwrap()
+}
@@ -0,0 +1,157 @@
+extern crate rhubarb;
+#[macro_use]
+extern crate serde_json;
+
+use rhubarb::layout::{Layout, Component, ComponentIndex, Panel};
+use rhubarb::props::{Align, PanelKind};
+use rhubarb::container::{Container, Tabs, Accordion, Modal, TabChange, ModalClose};
+use rhubarb::control::button::{ButtonControl, ButtonClick};
+use rhubarb::chart::{ChartComponent, ChartClick, ChartSelect, PointRef};
+use rhubarb::event::{Event, EventMessage};
+use rhubarb::error::{self, RhubarbError};
+
+#[test]
+fn duplicate_names_rejected() {
+    let mut layout: Layout<u32> = Layout::new();
+    let panel = layout.add_panel("main_panel", None).unwrap();
+    let button = layout.add_control_to_panel("button", ButtonControl::new("Go"), panel).unwrap();
+
+    match layout.add_control_to_panel("button", ButtonControl::new("Again"), panel) {
+        Err(RhubarbError::DuplicateComponent(name)) => assert_eq!(name, "button"),
+        other => panic!("expected duplicate component error, got {:?}", other),
+    }
+    match layout.add_panel("main_panel", Some(panel)) {
+        Err(RhubarbError::DuplicateComponent(name)) => assert_eq!(name, "main_panel"),
+        other => panic!("expected duplicate component error, got {:?}", other),
+    }
+
+    // neither the registry nor the panels were modified
+    assert_eq!(layout.get_component_index("button"), Some(button));
+    assert_eq!(layout.get_component_index("main_panel"), Some(panel));
+    assert_eq!(layout.children(None).unwrap(), &[panel]);
+    assert_eq!(layout.children(Some(panel)).unwrap(), &[button]);
+    assert_eq!(layout.add_panel("other_panel", None).unwrap(), button + 1);
+}
+
+fn tabs_active(layout: &Layout<u32>, idx: ComponentIndex) -> usize {
+    match layout.get(idx) {
+        Some(&Component::Container(Container::Tabs(ref tabs))) => tabs.active,
+        other => panic!("expected tabs, got {:?}", other),
+    }
+}
+
+fn modal_open(layout: &Layout<u32>, idx: ComponentIndex) -> bool {
+    match layout.get(idx) {
+        Some(&Component::Container(Container::Modal(ref modal))) => modal.open,
+        other => panic!("expected modal, got {:?}", other),
+    }
+}
+
+#[test]
+fn containers_accept_children() {
+    let mut layout: Layout<u32> = Layout::new();
+    let tabs = layout.add_container("tabs", Tabs::new(0), None).unwrap();
+    let first = layout.add_panel("first", Some(tabs)).unwrap();
+    let second = layout.add_panel("second", Some(tabs)).unwrap();
+    let accordion = layout.add_container("accordion", Accordion::new(), Some(first)).unwrap();
+    let section = layout.add_panel("section", Some(accordion)).unwrap();
+    let modal = layout.add_container("modal", Modal::new(), None).unwrap();
+    let button = layout.add_control_to_panel("button", ButtonControl::new("Go"), modal).unwrap();
+
+    assert_eq!(layout.children(None).unwrap(), &[tabs, modal]);
+    assert_eq!(layout.children(Some(tabs)).unwrap(), &[first, second]);
+    assert_eq!(layout.children(Some(accordion)).unwrap(), &[section]);
+    assert_eq!(layout.children(Some(modal)).unwrap(), &[button]);
+    assert_eq!(layout.parent(section), Some(accordion));
+}
+
+#[test]
+fn accordion_rejects_events() {
+    let mut layout: Layout<u32> = Layout::new();
+    let accordion = layout.add_container("accordion", Accordion::new(), None).unwrap();
+    let message = EventMessage { idx: accordion, event: Event::TabChange(TabChange { idx: 0 }) };
+    match layout.handle_event(message, 0) {
+        Err(RhubarbError::InvalidEvent { component_type, .. }) =>
+            assert_eq!(component_type, "Accordion"),
+        other => panic!("expected invalid event error, got {:?}", other),
+    }
+}
+
+#[test]
+fn client_changes_synced() {
+    let mut layout: Layout<u32> = Layout::new();
+    let tabs = layout.add_container("tabs", Tabs::new(0), None).unwrap();
+    layout.add_panel("first", Some(tabs)).unwrap();
+    layout.add_panel("second", Some(tabs)).unwrap();
+    let mut modal = Modal::new();
+    modal.open = true;
+    let modal = layout.add_container("modal", modal, None).unwrap();
+
+    let change_tab = |idx| EventMessage { idx: tabs, event: Event::TabChange(TabChange { idx }) };
+    layout.sync_event(&mut change_tab(1));
+    assert_eq!(tabs_active(&layout, tabs), 1);
+    // tabs which don't exist are ignored
+    layout.sync_event(&mut change_tab(5));
+    assert_eq!(tabs_active(&layout, tabs), 1);
+
+    layout.sync_event(&mut EventMessage { idx: modal, event: Event::ModalClose(ModalClose {}) });
+    assert!(!modal_open(&layout, modal));
+}
+
+#[test]
+fn panel_kinds_serialized() {
+    let kind = |panel: Panel| serde_json::to_value(&panel).unwrap()["kind"].clone();
+    assert_eq!(kind(Panel::new()), json!({ "type": "column" }));
+    assert_eq!(kind(Panel::column()), json!({ "type": "column" }));
+    assert_eq!(kind(Panel::row()), json!({ "type": "row" }));
+    assert_eq!(kind(Panel::grid(3)), json!({ "type": "grid", "columns": 3 }));
+    assert_eq!(kind(Panel::with_kind(PanelKind::Grid { columns: 2 })),
+        json!({ "type": "grid", "columns": 2 }));
+
+    let panel = serde_json::to_value(&Panel::row().with_gap(8).with_align(Align::Center)
+        .with_justify(Align::SpaceBetween).with_wrap(true)).unwrap();
+    assert_eq!(panel["gap"], json!(8));
+    assert_eq!(panel["align"], json!("center"));
+    assert_eq!(panel["justify"], json!("space-between"));
+    assert_eq!(panel["wrap"], json!(true));
+    // unset layout options are left to the front-end
+    let panel = serde_json::to_value(&Panel::column()).unwrap();
+    assert!(panel.get("gap").is_none() && panel.get("align").is_none());
+    assert!(panel.get("justify").is_none());
+    assert_eq!(panel["wrap"], json!(false));
+}
+
+fn chart_layout() -> (Layout<usize>, ComponentIndex) {
+    let mut layout = Layout::new();
+    let chart = ChartComponent::new()
+        .with_on_click(|click: ChartClick, _: usize| -> error::Result<usize> {
+            Ok(click.points[0].point)
+        })
+        .with_on_select(|select: ChartSelect, _: usize| -> error::Result<usize> {
+            Ok(select.points.len())
+        });
+    let idx = layout.add_chart("chart", chart, None).unwrap();
+    (layout, idx)
+}
+
+#[test]
+fn chart_events_handled() {
+    let (layout, chart) = chart_layout();
+    let points = vec![PointRef { trace: 0, point: 4 }, PointRef { trace: 1, point: 2 }];
+    let click = Event::ChartClick(ChartClick { points: points[..1].to_vec() });
+    assert_eq!(layout.handle_event(EventMessage { idx: chart, event: click }, 0).unwrap(), 4);
+    let select = Event::ChartSelect(ChartSelect { points });
+    assert_eq!(layout.handle_event(EventMessage { idx: chart, event: select }, 0).unwrap(), 2);
+}
+
+#[test]
+fn chart_rejects_control_events() {
+    let (layout, chart) = chart_layout();
+    let click = Event::ButtonClick(ButtonClick {});
+    match layout.handle_event(EventMessage { idx: chart, event: click }, 0) {
+        Err(RhubarbError::InvalidEvent { component_type, .. }) => {
+            assert_eq!(component_type, "ChartComponent");
+        },
+        other => panic!("expected invalid event error, got {:?}", other),
+    }
+}
@@ -0,0 +1,168 @@
+extern crate rhubarb;
+#[macro_use]
+extern crate serde_json;
+
+use rhubarb::Chart;
+use rhubarb::layout::{Layout, Component, ComponentIndex};
+use rhubarb::control::Control;
+use rhubarb::control::timer::{TimerControl, Tick};
+use rhubarb::update::Update;
+use rhubarb::control::button::{ButtonControl, ButtonClick};
+use rhubarb::event::{Event, EventMessage};
+use rhubarb::props::CommonProperties;
+use rhubarb::error::RhubarbError;
+
+fn sample_layout() -> (Layout<u32>, ComponentIndex) {
+    let mut layout = Layout::new();
+    let panel = layout.add_panel("main_panel", None).unwrap();
+    layout.add_control_to_panel("button", ButtonControl::new("Go"), panel).unwrap();
+    (layout, panel)
+}
+
+#[test]
+fn failed_changes_leave_layout_unchanged() {
+    let (mut layout, panel) = sample_layout();
+    let mut update = Update::new(None::<Chart>, 0u32);
+    update.add_panel("filter_panel", Some(panel), None);
+    update.remove_component(42);
+    match update.apply_layout_changes(&mut layout) {
+        Err(RhubarbError::UnknownComponent(42)) => {},
+        other => panic!("expected unknown component error, got {:?}", other),
+    }
+    assert_eq!(layout.get_component_index("filter_panel"), None);
+    assert_eq!(layout.children(Some(panel)).unwrap().len(), 1);
+}
+
+#[test]
+fn repeated_changes_are_skipped() {
+    let (mut layout, panel) = sample_layout();
+    for _ in 0..2 {
+        let mut update = Update::new(None::<Chart>, 0u32);
+        update.add_panel("filter_panel", Some(panel), None);
+        update.apply_layout_changes(&mut layout).unwrap();
+    }
+    assert_eq!(layout.children(Some(panel)).unwrap().len(), 2);
+
+    let button = layout.get_component_index("button").unwrap();
+    for _ in 0..2 {
+        let mut update = Update::new(None::<Chart>, 0u32);
+        update.remove_component(button);
+        update.apply_layout_changes(&mut layout).unwrap();
+    }
+    assert!(layout.is_removed(button));
+    assert_eq!(layout.children(Some(panel)).unwrap().len(), 1);
+}
+
+#[test]
+fn events_for_removed_components_rejected() {
+    let (mut layout, _) = sample_layout();
+    let button = layout.get_component_index("button").unwrap();
+    let mut update = Update::new(None::<Chart>, 0u32);
+    update.remove_component(button);
+    update.apply_layout_changes(&mut layout).unwrap();
+
+    let mut click = EventMessage { idx: button, event: Event::ButtonClick(ButtonClick {}) };
+    layout.sync_event(&mut click);
+    match layout.handle_event(click, 0) {
+        Err(RhubarbError::UnknownComponent(idx)) => assert_eq!(idx, button),
+        other => panic!("expected unknown component error, got {:?}", other),
+    }
+}
+
+#[test]
+fn disabling_components_only_affects_session() {
+    let (base, _) = sample_layout();
+    let button = base.get_component_index("button").unwrap();
+    let click = || EventMessage { idx: button, event: Event::ButtonClick(ButtonClick {}) };
+
+    let mut session_layout = base.clone();
+    let mut update = Update::new(None::<Chart>, 0u32);
+    update.add_component(button, ButtonControl::<u32>::new("Go").with_enabled(false)).unwrap();
+    assert!(!update.modifies_layout());
+    update.apply_layout_changes(&mut session_layout).unwrap();
+
+    match session_layout.handle_event(click(), 0) {
+        Err(RhubarbError::DisabledComponent(idx)) => assert_eq!(idx, button),
+        other => panic!("expected disabled component error, got {:?}", other),
+    }
+    assert_eq!(base.handle_event(click(), 0).unwrap(), 0);
+}
+
+fn timer_running(layout: &Layout<u32>, idx: ComponentIndex) -> bool {
+    match layout.get(idx) {
+        Some(&Component::Control(Control::Timer(ref timer))) => timer.running,
+        other => panic!("expected timer, got {:?}", other),
+    }
+}
+
+/// Sync a tick (with a bogus count) from `timer`, returning the tick count it's replaced with.
+fn tick(layout: &mut Layout<u32>, timer: ComponentIndex) -> u64 {
+    let mut event = EventMessage { idx: timer, event: Event::Tick(Tick { count: 100 }) };
+    layout.sync_event(&mut event);
+    match event.event {
+        Event::Tick(tick) => tick.count,
+        other => panic!("expected tick, got {:?}", other),
+    }
+}
+
+#[test]
+fn timers_stop_after_max_ticks() {
+    let (mut layout, panel) = sample_layout();
+    let timer = layout.add_control_to_panel("timer",
+        TimerControl::new(100).with_running(true).with_max_ticks(3), panel).unwrap();
+    // tick counts sent by the client are replaced by the counts kept in the layout
+    assert_eq!(tick(&mut layout, timer), 1);
+    assert_eq!(tick(&mut layout, timer), 2);
+    assert!(timer_running(&layout, timer));
+    assert_eq!(tick(&mut layout, timer), 3);
+    assert!(!timer_running(&layout, timer));
+    // ticks sent after the timer stopped aren't counted
+    assert_eq!(tick(&mut layout, timer), 3);
+
+    // restarting the timer restarts its count
+    let mut update = Update::new(None::<Chart>, 0u32);
+    update.set_timer_running(&layout, timer, true).unwrap();
+    update.apply_layout_changes(&mut layout).unwrap();
+    assert!(timer_running(&layout, timer));
+    assert_eq!(tick(&mut layout, timer), 1);
+}
+
+#[test]
+fn common_props_flattened() {
+    let button: Control<u32> = ButtonControl::new("Go").with_visible(false).with_class("primary")
+        .into();
+    let json = serde_json::to_value(&button).unwrap();
+    assert_eq!(json["type"], json!("button"));
+    assert_eq!(json["properties"]["text"], json!("Go"));
+    assert_eq!(json["properties"]["visible"], json!(false));
+    assert_eq!(json["properties"]["enabled"], json!(true));
+    assert_eq!(json["properties"]["class"], json!("primary"));
+    assert!(json["properties"].get("common").is_none());
+}
+
+#[test]
+fn common_props_edited_by_updates() {
+    let (mut layout, _) = sample_layout();
+    let button = layout.get_component_index("button").unwrap();
+    let mut update = Update::new(None::<Chart>, 0u32);
+    update.edit_component(&layout, button, |component| {
+        let common = component.common_mut();
+        common.visible = false;
+        common.class = Some("highlighted".into());
+        Ok(())
+    }).unwrap();
+
+    // the edited properties are sent to the client...
+    let json = serde_json::to_value(&update).unwrap();
+    assert_eq!(json["components"][0]["idx"], json!(button));
+    let properties = &json["components"][0]["control"]["properties"];
+    assert_eq!(properties["visible"], json!(false));
+    assert_eq!(properties["class"], json!("highlighted"));
+    assert_eq!(properties["text"], json!("Go"));
+
+    // ...and kept in the layout
+    update.apply_layout_changes(&mut layout).unwrap();
+    let common = layout.get(button).unwrap().common();
+    assert!(!common.visible);
+    assert_eq!(common.class, Some("highlighted".to_string()));
+}
@@ -0,0 +1,46 @@
+extern crate rhubarb;
+
+use rhubarb::chart::{ChartSelect, PointRef};
+use rhubarb::linked::{LinkedSelection, RowMap, RowSet};
+
+fn point(trace: usize, point: usize) -> PointRef {
+    PointRef { trace, point }
+}
+
+#[test]
+fn selection_maps_to_rows() {
+    let mut row_map = RowMap::new();
+    assert_eq!(row_map.add_trace(vec![4, 2, 0]), 0);
+    assert_eq!(row_map.add_trace(vec![1, 3]), 1);
+
+    let mut selection = LinkedSelection::new();
+    assert_eq!(selection.rows(&row_map), None);
+
+    // points outside of the row map are ignored
+    selection.select(7, ChartSelect { points: vec![point(0, 1), point(1, 1), point(1, 5)] });
+    assert_eq!(selection.source(), Some(7));
+    let rows = selection.rows(&row_map).unwrap();
+    assert_eq!(rows.iter().collect::<Vec<_>>(), vec![2, 3]);
+
+    // selecting nothing clears the selection
+    selection.select(7, ChartSelect { points: vec![] });
+    assert!(!selection.is_active());
+    assert_eq!(selection.rows(&row_map), None);
+}
+
+#[test]
+fn filtered_row_map() {
+    let row_map = RowMap::filtered(6, |row| row % 2 == 1);
+    assert_eq!(row_map.num_traces(), 1);
+    assert_eq!(row_map.trace_rows(0), Some(&[1, 3, 5][..]));
+    assert_eq!(row_map.row(point(0, 1)), Some(3));
+    assert_eq!(RowMap::identity(3).trace_rows(0), Some(&[0, 1, 2][..]));
+}
+
+#[test]
+fn selected_points_of_trace() {
+    let rows: RowSet = vec![1, 3].into_iter().collect();
+    assert_eq!(rows.selected_points(&[3, 0, 1, 2]), vec![0, 2]);
+    assert_eq!(rows.selected_points(&[0, 2]), Vec::<usize>::new());
+    assert_eq!(rows.filter(&[3, 0, 1, 2]), vec![3, 1]);
+}
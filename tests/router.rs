@@ -0,0 +1,35 @@
+extern crate rhubarb;
+
+use std::path::PathBuf;
+
+use rhubarb::{Chart, RhubarbApp};
+use rhubarb::error::{self, RhubarbError};
+use rhubarb::layout::Layout;
+use rhubarb::update::Update;
+
+fn generate(_: &Layout<u32>, _: Option<u32>, state: u32) -> error::Result<Update<u32>> {
+    Ok(Update::new(None::<Chart>, state))
+}
+
+fn asset(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data/assets").join(name)
+}
+
+#[test]
+fn vendored_plotly_loaded() {
+    let app = RhubarbApp::new(Layout::new()).offline_plotly(asset("plotly.min.js"));
+    if let Err(e) = app.router(generate, "/") {
+        panic!("expected router, got {:?}", e);
+    }
+}
+
+#[test]
+fn missing_vendored_plotly_rejected() {
+    let path = asset("missing.js");
+    let app = RhubarbApp::new(Layout::new()).offline_plotly(&path);
+    match app.router(generate, "/") {
+        Err(RhubarbError::MissingAsset { path: ref missing, .. }) => assert_eq!(missing, &path),
+        Err(other) => panic!("expected missing asset error, got {:?}", other),
+        Ok(_) => panic!("expected missing asset error, got a router"),
+    }
+}
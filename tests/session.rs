@@ -0,0 +1,66 @@
+extern crate rhubarb;
+
+use std::collections::HashSet;
+
+use rhubarb::layout::Layout;
+use rhubarb::session::{self, Session, Sessions, MAX_SESSIONS, MAX_NEW_SESSIONS};
+use rhubarb::error::RhubarbError;
+
+#[test]
+fn tokens_are_random() {
+    let tokens = (0..100).map(|_| session::new_token()).collect::<HashSet<_>>();
+    assert_eq!(tokens.len(), 100);
+    assert!(tokens.iter().all(|token| token.len() >= 22));
+}
+
+#[test]
+fn unknown_sessions_rejected() {
+    let sessions = Sessions::<u32>::new();
+    match sessions.get("not-a-session") {
+        Err(RhubarbError::InvalidSession(_)) => {},
+        other => panic!("expected invalid session error, got {:?}", other),
+    }
+    // rejected sessions aren't created
+    assert!(sessions.is_empty());
+
+    let token = session::new_token();
+    sessions.insert(token.clone(), Session::new(Layout::new(), Some(5)));
+    let found = sessions.get(&token).unwrap();
+    assert!(session::lock(&found).history.is_some());
+}
+
+#[test]
+fn least_recently_used_forgotten() {
+    let sessions = Sessions::<u32>::new();
+    let tokens = (0..MAX_SESSIONS + 1).map(|_| session::new_token()).collect::<Vec<_>>();
+    // sessions become active once they're used
+    for token in &tokens[..MAX_SESSIONS] {
+        sessions.insert(token.clone(), Session::new(Layout::new(), None));
+        sessions.get(token).unwrap();
+    }
+    // using the oldest session keeps it alive
+    sessions.get(&tokens[0]).unwrap();
+    sessions.insert(tokens[MAX_SESSIONS].clone(), Session::new(Layout::new(), None));
+    sessions.get(&tokens[MAX_SESSIONS]).unwrap();
+    assert_eq!(sessions.len(), MAX_SESSIONS);
+    assert!(sessions.get(&tokens[0]).is_ok());
+    assert!(sessions.get(&tokens[1]).is_err());
+}
+
+#[test]
+fn new_sessions_dont_evict_active_sessions() {
+    let sessions = Sessions::<u32>::new();
+    let active = session::new_token();
+    sessions.insert(active.clone(), Session::new(Layout::new(), None));
+    sessions.get(&active).unwrap();
+
+    // a burst of page loads only forgets other unused sessions
+    let tokens = (0..MAX_NEW_SESSIONS + 1).map(|_| session::new_token()).collect::<Vec<_>>();
+    for token in &tokens {
+        sessions.insert(token.clone(), Session::new(Layout::new(), None));
+    }
+    assert_eq!(sessions.len(), MAX_NEW_SESSIONS + 1);
+    assert!(sessions.get(&active).is_ok());
+    assert!(sessions.get(&tokens[0]).is_err());
+    assert!(sessions.get(&tokens[1]).is_ok());
+}
@@ -0,0 +1,52 @@
+extern crate rhubarb;
+#[macro_use] extern crate serde_derive;
+
+use rhubarb::share::{encode_state, decode_state, state_param, initial_state};
+use rhubarb::error::RhubarbError;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct SampleState {
+    year: u32,
+    countries: Vec<String>,
+}
+
+fn sample_state() -> SampleState {
+    SampleState { year: 1995, countries: vec!["Canada".into(), "Côte d'Ivoire".into()] }
+}
+
+#[test]
+fn encode_decode_roundtrip() {
+    let encoded = encode_state(&sample_state()).unwrap();
+    assert!(encoded.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "not URL-safe: {}", encoded);
+    assert_eq!(decode_state::<SampleState>(&encoded).unwrap(), sample_state());
+}
+
+#[test]
+fn state_param_in_query() {
+    assert_eq!(state_param("state=abc"), Some("abc"));
+    assert_eq!(state_param("page=2&state=abc&x=1"), Some("abc"));
+    assert_eq!(state_param("state="), None);
+    assert_eq!(state_param("states=abc"), None);
+    assert_eq!(state_param(""), None);
+}
+
+#[test]
+fn invalid_states_rejected() {
+    // not base64
+    match decode_state::<SampleState>("not a state!") {
+        Err(RhubarbError::InvalidState(_)) => {},
+        other => panic!("expected invalid state error, got {:?}", other),
+    }
+    // valid encoding of a different type
+    let encoded = encode_state(&vec![1, 2, 3]).unwrap();
+    match decode_state::<SampleState>(&encoded) {
+        Err(RhubarbError::InvalidState(_)) => {},
+        other => panic!("expected invalid state error, got {:?}", other),
+    }
+    match initial_state::<SampleState>(Some("state=%%%")) {
+        Err(RhubarbError::InvalidState(_)) => {},
+        other => panic!("expected invalid state error, got {:?}", other),
+    }
+    assert_eq!(initial_state::<SampleState>(None).unwrap(), SampleState::default());
+}
@@ -0,0 +1,92 @@
+extern crate rhubarb;
+
+use std::env;
+use std::fs;
+use std::sync::Arc;
+
+use rhubarb::store::{ViewStore, JsonFileStore, save_view, load_view};
+use rhubarb::control::view::LoadViewControl;
+use rhubarb::error::RhubarbError;
+
+#[test]
+fn json_file_store_roundtrip() {
+    let dir = env::temp_dir().join(format!("rhubarb-store-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("views.json");
+    let _ = fs::remove_file(&path);
+    let store = JsonFileStore::new(&path);
+    assert_eq!(store.names().unwrap(), Vec::<String>::new());
+
+    save_view(&store, " 1990s ", &vec![1990u32, 1999]).unwrap();
+    save_view(&store, "1980s", &vec![1980u32, 1989]).unwrap();
+    assert_eq!(store.names().unwrap(), vec!["1980s", "1990s"]);
+    assert_eq!(load_view::<Vec<u32>>(&store, "1990s ").unwrap(), vec![1990, 1999]);
+
+    // views are kept in the file
+    let reopened = JsonFileStore::new(&path);
+    assert_eq!(load_view::<Vec<u32>>(&reopened, "1980s").unwrap(), vec![1980, 1989]);
+
+    assert!(store.remove("1980s").unwrap());
+    assert!(!store.remove("1980s").unwrap());
+    assert_eq!(store.names().unwrap(), vec!["1990s"]);
+    match load_view::<Vec<u32>>(&store, "1980s") {
+        Err(RhubarbError::InvalidView(_)) => {},
+        other => panic!("expected invalid view error, got {:?}", other),
+    }
+    match save_view(&store, "  ", &vec![0u32]) {
+        Err(RhubarbError::InvalidView(_)) => {},
+        other => panic!("expected invalid view error, got {:?}", other),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_store_roundtrip() {
+    use rhubarb::store::SqliteStore;
+
+    let dir = env::temp_dir().join(format!("rhubarb-store-sqlite-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("views.db");
+    let _ = fs::remove_file(&path);
+    {
+        let store = SqliteStore::open(&path).unwrap();
+        assert_eq!(store.names().unwrap(), Vec::<String>::new());
+        save_view(&store, "1990s", &vec![1990u32, 1999]).unwrap();
+        save_view(&store, "1980s", &vec![1980u32, 1989]).unwrap();
+        // saving an existing view replaces it
+        save_view(&store, "1990s", &vec![1990u32, 1995]).unwrap();
+        assert_eq!(store.names().unwrap(), vec!["1980s", "1990s"]);
+        assert_eq!(load_view::<Vec<u32>>(&store, "1990s").unwrap(), vec![1990, 1995]);
+    }
+
+    // views are kept in the database
+    let store = SqliteStore::open(&path).unwrap();
+    assert_eq!(load_view::<Vec<u32>>(&store, "1980s").unwrap(), vec![1980, 1989]);
+    assert!(store.remove("1980s").unwrap());
+    assert!(!store.remove("1980s").unwrap());
+    assert_eq!(store.names().unwrap(), vec!["1990s"]);
+    match load_view::<Vec<u32>>(&store, "1980s") {
+        Err(RhubarbError::InvalidView(_)) => {},
+        other => panic!("expected invalid view error, got {:?}", other),
+    }
+    drop(store);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn load_view_control_refresh() {
+    let dir = env::temp_dir().join(format!("rhubarb-store-control-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("views.json");
+    let _ = fs::remove_file(&path);
+    let store: Arc<ViewStore> = Arc::new(JsonFileStore::new(&path));
+
+    let mut control = LoadViewControl::<Vec<u32>>::new(store.clone());
+    assert!(control.views().is_empty());
+    save_view(&*store, "all", &vec![0u32]).unwrap();
+    assert!(control.views().is_empty());
+    control.refresh().unwrap();
+    assert_eq!(control.views(), &["all".to_string()]);
+    fs::remove_dir_all(&dir).unwrap();
+}